@@ -0,0 +1,26 @@
+#!/usr/bin/env sh
+# Times every benchmark script on both backends.
+#
+# Usage: bench/run.sh [path/to/taco-rs]
+#
+#   loop.taco         arithmetic and variable access in a tight loop
+#   fib.taco          recursive function calls
+#   nested.taco       a deeply nested expression evaluated many times
+#   collections.taco  list and map reads and writes
+#
+# Build with `cargo build --release` first. Script output goes to /dev/null,
+# so only the timings are shown.
+
+set -e
+
+taco=${1:-target/release/taco-rs}
+dir=$(dirname "$0")
+
+for script in "$dir"/*.taco; do
+    for backend in tree vm; do
+        start=$(date +%s%N)
+        "$taco" --backend "$backend" "$script" > /dev/null
+        end=$(date +%s%N)
+        printf '%-20s %-5s %6d ms\n' "$(basename "$script")" "$backend" $(( (end - start) / 1000000 ))
+    done
+done
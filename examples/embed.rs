@@ -0,0 +1,28 @@
+//! Runs a small taco script from Rust and calls back into it.
+
+use taco::{Arity, Engine, Value};
+
+fn main() -> Result<(), taco::Error> {
+    let mut engine = Engine::new();
+
+    engine.set_global("rate", Value::Integer(20));
+    engine.define_native("log", Arity::Exact(1), |interpreter, args| {
+        println!("[script] {}", interpreter.stringify(args[0].clone()));
+        Ok(Value::Nil)
+    });
+
+    engine.eval(
+        "taco tax(amount) {
+            log(\"computing tax\");
+            return amount * rate / 100;
+        }",
+    )?;
+
+    let tax = engine.call_function("tax", vec![Value::Integer(150)])?;
+    println!("tax: {:?}", tax);
+
+    let total = engine.eval("150 + tax(150);")?;
+    println!("total: {:?}", total);
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+#[derive(Debug, Clone)]
+pub struct AssignExpr {
+    name: Token,
+    value: Expr,
+}
+
+into_expr!(AssignExpr);
+
+impl AssignExpr {
+    pub fn new(name: Token, value: Expr) -> AssignExpr {
+        AssignExpr { name, value }
+    }
+
+    pub fn get_name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn get_value(&self) -> &Expr {
+        &self.value
+    }
+}
@@ -14,15 +14,15 @@ impl BinaryExpr {
     BinaryExpr { left, operator, right }
   }
 
-  pub fn get_left(&self) -> Expr {
-    self.left.clone()
+  pub fn get_left(&self) -> &Expr {
+    &self.left
   }
 
   pub fn get_operator(&self) -> &Token {
     &self.operator
   }
 
-  pub fn get_right(&self) -> Expr {
-    self.right.clone()
+  pub fn get_right(&self) -> &Expr {
+    &self.right
   }
 }
\ No newline at end of file
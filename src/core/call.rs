@@ -0,0 +1,32 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+#[derive(Debug, Clone)]
+pub struct CallExpr {
+    callee: Expr,
+    paren: Token,
+    arguments: Vec<Expr>,
+}
+
+into_expr!(CallExpr);
+
+impl CallExpr {
+    pub fn new(callee: Expr, paren: Token, arguments: Vec<Expr>) -> CallExpr {
+        CallExpr {
+            callee,
+            paren,
+            arguments,
+        }
+    }
+
+    pub fn get_callee(&self) -> &Expr {
+        &self.callee
+    }
+
+    pub fn get_paren(&self) -> &Token {
+        &self.paren
+    }
+
+    pub fn get_arguments(&self) -> &Vec<Expr> {
+        &self.arguments
+    }
+}
@@ -1,6 +1,15 @@
+use crate::core::assign::AssignExpr;
 use crate::core::binary::BinaryExpr;
+use crate::core::call::CallExpr;
+use crate::core::get::GetExpr;
 use crate::core::grouping::GroupingExpr;
+use crate::core::index::IndexExpr;
+use crate::core::list::ListExpr;
 use crate::core::literal::Literal;
+use crate::core::logical::LogicalExpr;
+use crate::core::map::MapExpr;
+use crate::core::set_index::SetIndexExpr;
+use crate::core::slice::SliceExpr;
 use crate::core::unary::UnaryExpr;
 use crate::token::tokens::Token;
 
@@ -9,7 +18,38 @@ pub enum Expr {
     BinaryExpr(Box<BinaryExpr>),
     GroupingExpr(Box<GroupingExpr>),
     UnaryExpr(Box<UnaryExpr>),
-    // LiteralExpr(Box<LiteralExpr<Box<dyn Any>>>)
     Literal(Literal),
     VarDeclaration(Token),
+    AssignExpr(Box<AssignExpr>),
+    MapExpr(Box<MapExpr>),
+    IndexExpr(Box<IndexExpr>),
+    SetIndexExpr(Box<SetIndexExpr>),
+    CallExpr(Box<CallExpr>),
+    GetExpr(Box<GetExpr>),
+    ListExpr(Box<ListExpr>),
+    SliceExpr(Box<SliceExpr>),
+    LogicalExpr(Box<LogicalExpr>),
+}
+
+impl Expr {
+    /// The line of the first token this expression keeps, if any. Literals
+    /// do not remember where they were written.
+    pub fn line(&self) -> Option<u32> {
+        match self {
+            Expr::BinaryExpr(binary) => Some(binary.get_operator().get_line()),
+            Expr::GroupingExpr(grouping) => grouping.expr.line(),
+            Expr::UnaryExpr(unary) => Some(unary.get_operator().get_line()),
+            Expr::Literal(_) => None,
+            Expr::VarDeclaration(name) => Some(name.get_line()),
+            Expr::AssignExpr(assign) => Some(assign.get_name().get_line()),
+            Expr::MapExpr(map) => Some(map.get_brace().get_line()),
+            Expr::IndexExpr(index) => Some(index.get_bracket().get_line()),
+            Expr::SetIndexExpr(set) => Some(set.get_bracket().get_line()),
+            Expr::CallExpr(call) => Some(call.get_paren().get_line()),
+            Expr::GetExpr(get) => Some(get.get_name().get_line()),
+            Expr::ListExpr(list) => Some(list.get_bracket().get_line()),
+            Expr::SliceExpr(slice) => Some(slice.get_bracket().get_line()),
+            Expr::LogicalExpr(logical) => Some(logical.get_operator().get_line()),
+        }
+    }
 }
@@ -0,0 +1,23 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+#[derive(Debug, Clone)]
+pub struct GetExpr {
+    object: Expr,
+    name: Token,
+}
+
+into_expr!(GetExpr);
+
+impl GetExpr {
+    pub fn new(object: Expr, name: Token) -> GetExpr {
+        GetExpr { object, name }
+    }
+
+    pub fn get_object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn get_name(&self) -> &Token {
+        &self.name
+    }
+}
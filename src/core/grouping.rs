@@ -9,6 +9,6 @@ into_expr!(GroupingExpr);
 
 impl GroupingExpr {
   pub fn new(expr: Expr) -> GroupingExpr {
-    GroupingExpr { expr: expr }
+    GroupingExpr { expr }
   }
 }
\ No newline at end of file
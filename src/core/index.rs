@@ -0,0 +1,32 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+#[derive(Debug, Clone)]
+pub struct IndexExpr {
+    object: Expr,
+    bracket: Token,
+    index: Expr,
+}
+
+into_expr!(IndexExpr);
+
+impl IndexExpr {
+    pub fn new(object: Expr, bracket: Token, index: Expr) -> IndexExpr {
+        IndexExpr {
+            object,
+            bracket,
+            index,
+        }
+    }
+
+    pub fn get_object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn get_bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn get_index(&self) -> &Expr {
+        &self.index
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+#[derive(Debug, Clone)]
+pub struct ListExpr {
+    bracket: Token,
+    elements: Vec<Expr>,
+}
+
+into_expr!(ListExpr);
+
+impl ListExpr {
+    pub fn new(bracket: Token, elements: Vec<Expr>) -> ListExpr {
+        ListExpr { bracket, elements }
+    }
+
+    pub fn get_bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn get_elements(&self) -> &Vec<Expr> {
+        &self.elements
+    }
+}
@@ -1,15 +1,5 @@
 use crate::core::expression::Expr;
 
-pub struct LiteralExpr<T> {
-  value: T
-}
-
-impl<T> LiteralExpr<T> {
-  pub fn new(value: T) -> LiteralExpr<T> {
-    LiteralExpr { value: value }
-  }
-}
-
 #[derive(Debug, Clone)]
 pub enum Literal {
   String(String),
@@ -19,9 +9,9 @@ pub enum Literal {
   Nil
 }
 
-impl Into<Expr> for Literal {
-  fn into(self) -> Expr {
-      Expr::Literal(self)
+impl From<Literal> for Expr {
+  fn from(val: Literal) -> Self {
+      Expr::Literal(val)
   }
 }
 
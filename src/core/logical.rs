@@ -0,0 +1,34 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+/// `and` / `or`. Unlike a binary expression, the right operand is only
+/// evaluated when the left one does not decide the result.
+#[derive(Debug, Clone)]
+pub struct LogicalExpr {
+    left: Expr,
+    operator: Token,
+    right: Expr,
+}
+
+into_expr!(LogicalExpr);
+
+impl LogicalExpr {
+    pub fn new(left: Expr, operator: Token, right: Expr) -> LogicalExpr {
+        LogicalExpr {
+            left,
+            operator,
+            right,
+        }
+    }
+
+    pub fn get_left(&self) -> &Expr {
+        &self.left
+    }
+
+    pub fn get_operator(&self) -> &Token {
+        &self.operator
+    }
+
+    pub fn get_right(&self) -> &Expr {
+        &self.right
+    }
+}
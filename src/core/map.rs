@@ -0,0 +1,23 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+#[derive(Debug, Clone)]
+pub struct MapExpr {
+    brace: Token,
+    entries: Vec<(Expr, Expr)>,
+}
+
+into_expr!(MapExpr);
+
+impl MapExpr {
+    pub fn new(brace: Token, entries: Vec<(Expr, Expr)>) -> MapExpr {
+        MapExpr { brace, entries }
+    }
+
+    pub fn get_brace(&self) -> &Token {
+        &self.brace
+    }
+
+    pub fn get_entries(&self) -> &Vec<(Expr, Expr)> {
+        &self.entries
+    }
+}
@@ -3,4 +3,13 @@ pub mod unary;
 pub mod binary;
 pub mod grouping;
 pub mod literal;
-pub mod traits;
\ No newline at end of file
+pub mod traits;
+pub mod assign;
+pub mod map;
+pub mod index;
+pub mod set_index;
+pub mod call;
+pub mod get;
+pub mod list;
+pub mod slice;
+pub mod logical;
\ No newline at end of file
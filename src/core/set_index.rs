@@ -0,0 +1,38 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+#[derive(Debug, Clone)]
+pub struct SetIndexExpr {
+    object: Expr,
+    bracket: Token,
+    index: Expr,
+    value: Expr,
+}
+
+into_expr!(SetIndexExpr);
+
+impl SetIndexExpr {
+    pub fn new(object: Expr, bracket: Token, index: Expr, value: Expr) -> SetIndexExpr {
+        SetIndexExpr {
+            object,
+            bracket,
+            index,
+            value,
+        }
+    }
+
+    pub fn get_object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn get_bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn get_index(&self) -> &Expr {
+        &self.index
+    }
+
+    pub fn get_value(&self) -> &Expr {
+        &self.value
+    }
+}
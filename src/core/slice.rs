@@ -0,0 +1,39 @@
+use crate::{core::expression::Expr, into_expr, token::tokens::Token};
+
+/// `object[start:end]`, where either bound may be left out.
+#[derive(Debug, Clone)]
+pub struct SliceExpr {
+    object: Expr,
+    bracket: Token,
+    start: Option<Expr>,
+    end: Option<Expr>,
+}
+
+into_expr!(SliceExpr);
+
+impl SliceExpr {
+    pub fn new(object: Expr, bracket: Token, start: Option<Expr>, end: Option<Expr>) -> SliceExpr {
+        SliceExpr {
+            object,
+            bracket,
+            start,
+            end,
+        }
+    }
+
+    pub fn get_object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn get_bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn get_start(&self) -> Option<&Expr> {
+        self.start.as_ref()
+    }
+
+    pub fn get_end(&self) -> Option<&Expr> {
+        self.end.as_ref()
+    }
+}
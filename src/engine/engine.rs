@@ -0,0 +1,95 @@
+use crate::{
+    error::{engine::Error, parser::scan_error},
+    interpreter::{
+        interpreter::{Interpreter, Value},
+        native::{Arity, HostFunction},
+        output::Output,
+    },
+    parser::parser::Parser,
+    token::scanner::Scanner,
+};
+
+/// Runs taco code on behalf of a Rust program.
+///
+/// Syntax and runtime errors are returned rather than reported, and nothing
+/// here ends the process. Values are reference counted without being `Send`, so
+/// an engine stays on the thread that created it.
+pub struct Engine {
+    interpreter: Interpreter,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::new()
+    }
+}
+
+impl Engine {
+    pub fn new() -> Engine {
+        Engine {
+            interpreter: Interpreter::new(),
+        }
+    }
+
+    /// An engine whose scripts write to `output` rather than the process's
+    /// streams.
+    pub fn with_output(output: Output) -> Engine {
+        Engine {
+            interpreter: Interpreter::with_output(output),
+        }
+    }
+
+    /// The underlying interpreter, for settings such as the backend, the
+    /// maximum call depth and resource limits.
+    pub fn interpreter(&mut self) -> &mut Interpreter {
+        &mut self.interpreter
+    }
+
+    /// Runs `source` and returns the value of its last statement if that is
+    /// an expression, or nil otherwise.
+    pub fn eval(&mut self, source: &str) -> Result<Value, Error> {
+        let mut scanner = Scanner::new(source.to_string());
+        let tokens = scanner.scan_tokens();
+        if let Some((line, message)) = scanner.errors.first() {
+            return Err(Error::Parse(scan_error(*line, message)));
+        }
+
+        let statements = Parser::new(tokens).parse()?;
+
+        Ok(self.interpreter.interpret_value(statements)?)
+    }
+
+    /// Binds `name` in the global scope, replacing any existing binding.
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        self.interpreter.set_global(name, value);
+    }
+
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.interpreter.get_global(name)
+    }
+
+    /// Calls the function bound to the global `name`.
+    pub fn call_function(&mut self, name: &str, arguments: Vec<Value>) -> Result<Value, Error> {
+        match self.interpreter.get_global(name) {
+            Some(function) => Ok(self.interpreter.call_value(function, arguments)?),
+            None => Err(Error::Undefined(name.to_string())),
+        }
+    }
+
+    /// Registers a host function that scripts can call by `name`.
+    pub fn define_native<F>(&mut self, name: &str, arity: Arity, function: F)
+    where
+        F: Fn(&mut Interpreter, Vec<Value>) -> Result<Value, String> + 'static,
+    {
+        self.interpreter.define_native(name, arity, function);
+    }
+
+    /// Registers a host function with typed arguments, such as
+    /// `|name: String, times: i64| name.repeat(times as usize)`.
+    pub fn define_fn<F, Args>(&mut self, name: &str, function: F)
+    where
+        F: HostFunction<Args> + 'static,
+    {
+        self.interpreter.define_fn(name, function);
+    }
+}
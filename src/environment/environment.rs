@@ -1,42 +1,99 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, rc::Rc};
 
-use crate::interpreter::interpreter::Value;
+use crate::{
+    error::interpreter::RuntimeError,
+    interpreter::interpreter::Value,
+    token::{
+        symbol::{Symbol, SymbolMap},
+        tokens::Token,
+    },
+};
 
 #[derive(Debug, Clone)]
 pub struct Environment {
-    values: HashMap<String, Value>,
-    enclosing: Option<Box<Environment>>,
+    values: SymbolMap<Value>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Environment::new()
+    }
 }
 
 impl Environment {
     pub fn new() -> Environment {
         Environment {
-            values: HashMap::new(),
+            values: SymbolMap::default(),
             enclosing: None,
         }
     }
 
-    pub fn define(&mut self, name: String, value: Value) {
+    pub fn new_enclosing(enclosing: Rc<RefCell<Environment>>) -> Environment {
+        Environment {
+            values: SymbolMap::default(),
+            enclosing: Some(enclosing),
+        }
+    }
+
+    pub fn get_enclosing(&self) -> Option<Rc<RefCell<Environment>>> {
+        self.enclosing.clone()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.values.values()
+    }
+
+    /// The variables defined in this scope itself, in no particular order.
+    pub fn bindings(&self) -> impl Iterator<Item = (&Symbol, &Value)> {
+        self.values.iter()
+    }
+
+    /// Drops every variable, which the cycle collector uses to break cycles
+    /// through scopes nothing can reach any more.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    pub fn define(&mut self, name: Symbol, value: Value) {
         self.values.insert(name, value);
     }
 
-    pub fn assign(&mut self, name: String, value: Value) {
-        if self.values.contains_key(&name) {
-            self.values.insert(name, value);
-        } else if let Some(enclosing) = &mut self.enclosing {
-            enclosing.assign(name, value);
+    /// The value bound to `name` in this scope or an enclosing one.
+    pub fn lookup(&self, name: Symbol) -> Option<Value> {
+        match self.values.get(&name) {
+            Some(value) => Some(value.clone()),
+            None => match &self.enclosing {
+                Some(enclosing) => enclosing.borrow().lookup(name),
+                None => None,
+            },
+        }
+    }
+
+    pub fn assign(&mut self, name: &Token, value: Value) -> Result<(), RuntimeError> {
+        if let Some(slot) = self.values.get_mut(&name.get_symbol()) {
+            *slot = value;
+            Ok(())
+        } else if let Some(enclosing) = &self.enclosing {
+            enclosing.borrow_mut().assign(name, value)
         } else {
-            panic!("Undefined variable '{}'.", name);
+            Err(RuntimeError::new(
+                name.clone(),
+                &format!("Undefined variable '{}'.", name.get_lexeme()),
+            ))
         }
     }
 
-    pub fn get(&self, name: String) -> Value {
-        if let Some(value) = self.values.get(&name) {
-            value.clone()
+    pub fn get(&self, name: &Token) -> Result<Value, RuntimeError> {
+        if let Some(value) = self.values.get(&name.get_symbol()) {
+            Ok(value.clone())
         } else if let Some(enclosing) = &self.enclosing {
-            enclosing.get(name)
+            enclosing.borrow().get(name)
         } else {
-            panic!("Undefined variable '{}'", name);
+            Err(RuntimeError::new(
+                name.clone(),
+                &format!("Undefined variable '{}'.", name.get_lexeme()),
+            ))
         }
     }
-}
\ No newline at end of file
+}
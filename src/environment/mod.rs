@@ -1 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod environment;
\ No newline at end of file
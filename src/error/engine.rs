@@ -0,0 +1,38 @@
+use std::fmt::{Display, Formatter};
+
+use crate::error::{interpreter::RuntimeError, parser::ParserError};
+
+/// Why the embedding API could not run something.
+#[derive(Debug)]
+pub enum Error {
+    /// The source did not parse.
+    Parse(ParserError),
+    /// The script raised an error, or exceeded one of its limits.
+    Runtime(RuntimeError),
+    /// `call_function` was given a name no global is bound to.
+    Undefined(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::Runtime(error) => write!(f, "{}", error),
+            Error::Undefined(name) => write!(f, "Undefined global '{}'.", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParserError> for Error {
+    fn from(error: ParserError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<RuntimeError> for Error {
+    fn from(error: RuntimeError) -> Self {
+        Error::Runtime(error)
+    }
+}
@@ -1,7 +1,9 @@
-pub fn report(line: u8, where_: &str, message: &str) -> () {
-  eprintln!("[Line {}] Error {}: {}", line, where_, message);
+use crate::interpreter::output::Output;
+
+pub fn report(output: &Output, line: u32, where_: &str, message: &str) {
+  output.eprintln(&format!("[Line {}] Error {}: {}", line, where_, message));
 }
 
-pub fn error(line: u8, message: &str) -> () {
-  report(line, "", message);
+pub fn error(output: &Output, line: u32, message: &str) {
+  report(output, line, "", message);
 }
\ No newline at end of file
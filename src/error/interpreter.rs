@@ -1,28 +1,103 @@
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    rc::Rc,
+};
 
-use crate::token::tokens::Token;
+use crate::{
+    interpreter::{error_value::ErrorValue, frame::StackFrame, interpreter::Value, output::Output},
+    token::tokens::Token,
+};
 
+/// What kind of failure a runtime error is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// An error in the script, raised by the interpreter or by `throw`.
+    Runtime,
+    /// Calls nested deeper than the interpreter's maximum depth.
+    StackOverflow,
+    /// The script executed more steps than its limit allows.
+    StepLimit,
+    /// The script allocated more bytes in total than its limit allows.
+    AllocationLimit,
+    /// The script printed more output than its limit allows.
+    OutputLimit,
+    /// The script ran past its deadline.
+    Timeout,
+}
+
+impl ErrorKind {
+    /// Whether this is one of the sandbox limits. Scripts cannot catch
+    /// these, and `finally` blocks do not run when they are raised.
+    pub fn is_limit(&self) -> bool {
+        matches!(
+            self,
+            ErrorKind::StepLimit | ErrorKind::AllocationLimit | ErrorKind::OutputLimit | ErrorKind::Timeout
+        )
+    }
+}
+
+#[derive(Debug)]
 pub struct RuntimeError {
-    pub token: Token,
-    pub message: String,
+    pub token: Box<Token>,
+    pub kind: ErrorKind,
+    pub message: Box<str>,
+    /// The module the error was raised in, when it is not the main script.
+    pub file: Option<Box<str>>,
+    /// The value passed to `throw`, for errors raised by scripts.
+    pub value: Option<Box<Value>>,
+    /// The calls that were active when the error was raised, innermost
+    /// first. Filled in as the error leaves the interpreter.
+    pub trace: Box<[StackFrame]>,
 }
 
 impl Display for RuntimeError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "[Line {}] Error: {}",
-            self.token.get_line(),
-            self.message
-        )
+        write!(f, "[{}] Error: {}", self.location(), self.message)
     }
 }
 
 impl RuntimeError {
     pub fn new(token: Token, message: &str) -> RuntimeError {
         RuntimeError {
-            token,
-            message: message.to_string(),
+            token: Box::new(token),
+            kind: ErrorKind::Runtime,
+            message: message.into(),
+            file: None,
+            value: None,
+            trace: Box::new([]),
+        }
+    }
+
+    /// An error raised by a script's `throw` statement.
+    pub fn thrown(token: Token, value: Value, message: &str) -> RuntimeError {
+        RuntimeError {
+            token: Box::new(token),
+            kind: ErrorKind::Runtime,
+            message: message.into(),
+            file: None,
+            value: Some(Box::new(value)),
+            trace: Box::new([]),
+        }
+    }
+
+    pub fn with_kind(mut self, kind: ErrorKind) -> RuntimeError {
+        self.kind = kind;
+        self
+    }
+
+    pub fn get_kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The value a `catch` clause binds: whatever was thrown, or an error
+    /// value describing an error raised by the interpreter itself.
+    pub fn to_value(&self) -> Value {
+        match &self.value {
+            Some(value) => value.as_ref().clone(),
+            None => Value::Error(Rc::new(ErrorValue::new(
+                &self.message,
+                self.token.get_line(),
+            ))),
         }
     }
 
@@ -33,9 +108,57 @@ impl RuntimeError {
     pub fn get_message(&self) -> &str {
         &self.message
     }
+
+    pub fn get_trace(&self) -> &[StackFrame] {
+        &self.trace
+    }
+
+    pub fn get_file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Records the file the error came from unless one is already known.
+    pub fn in_file(mut self, file: &str) -> RuntimeError {
+        if self.file.is_none() {
+            self.file = Some(file.into());
+        }
+
+        self
+    }
+
+    fn location(&self) -> String {
+        match &self.file {
+            Some(file) => format!("Line {} in {}", self.token.get_line(), file),
+            None => format!("Line {}", self.token.get_line()),
+        }
+    }
 }
 
-pub fn throw_runtime_error(error: RuntimeError) -> RuntimeError {
-    eprintln!("[Line {}]: {}", error.get_token().get_line(), error.get_message());
+/// Reports `error` and its traceback on `output`'s stderr.
+pub fn throw_runtime_error(output: &Output, error: RuntimeError) -> RuntimeError {
+    output.eprintln(&format!("[{}]: {}", error.location(), error.get_message()));
+
+    // A lone frame is just the script, which the location already names.
+    if error.trace.len() > 1 {
+        output.eprintln("Traceback (innermost first):");
+
+        // Deep recursion repeats the same frame many times; show it once.
+        let mut index = 0;
+        while index < error.trace.len() {
+            let frame = &error.trace[index];
+            let repeats = error.trace[index..]
+                .iter()
+                .take_while(|other| *other == frame)
+                .count();
+
+            output.eprintln(&format!("  {}", frame));
+            if repeats > 1 {
+                output.eprintln(&format!("  ... repeated {} more times", repeats - 1));
+            }
+
+            index += repeats;
+        }
+    }
+
     error
-}
\ No newline at end of file
+}
@@ -1,3 +1,4 @@
-pub mod handling;
+pub(crate) mod handling;
 pub mod parser;
-pub mod interpreter;
\ No newline at end of file
+pub mod interpreter;
+pub mod engine;
\ No newline at end of file
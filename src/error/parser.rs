@@ -2,21 +2,45 @@ use std::fmt::Display;
 
 use crate::token::tokens::{Token, TokenType};
 use crate::error::handling::report;
+use crate::interpreter::output::Output;
 
 pub fn throw_error(token: Token, message: &str) -> ParserError {
+  ParserError::new(vec![token], message)
+}
+
+/// Prints a syntax error for the user. The parser itself only returns
+/// errors, so that embedders can decide what to do with them.
+pub fn report_parser_error(output: &Output, error: &ParserError) {
+  let token = &error.token[0];
+
   if token.token_type == TokenType::Eof {
-    report(token.line as u8, " at end", message);
+    report(output, token.line, " at end", &error.message);
   } else {
-    report(token.line as u8, &format!(" at '{}'", token.lexeme), message);
+    report(output, token.line, &format!(" at '{}'", token.lexeme), &error.message);
   }
+}
+
+/// A lexical error from the scanner, as a syntax error at its line.
+pub fn scan_error(line: u32, message: &str) -> ParserError {
+  let token = Token::new(TokenType::Eof, String::new(), String::new(), line);
+  ParserError::new(vec![token], message)
+}
 
-  return ParserError::new(vec![token], message);
+/// What kind of syntax error a `ParserError` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+  /// Something in the source is wrong.
+  Invalid,
+  /// The source ended partway through a statement or expression, so more
+  /// input could still complete it.
+  UnexpectedEnd,
 }
 
 #[derive(Debug, Clone)]
 pub struct ParserError {
   pub token: Vec<Token>,
   pub message: String,
+  pub kind: ParseErrorKind,
 }
 
 impl Display for ParserError {
@@ -35,9 +59,19 @@ impl ParserError {
     ParserError {
       token,
       message: message.to_string(),
+      kind: ParseErrorKind::Invalid,
     }
   }
 
+  pub fn with_kind(mut self, kind: ParseErrorKind) -> ParserError {
+    self.kind = kind;
+    self
+  }
+
+  pub fn get_kind(&self) -> ParseErrorKind {
+    self.kind
+  }
+
   pub fn get_token(&self) -> &Vec<Token> {
     &self.token
   }
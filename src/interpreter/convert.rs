@@ -0,0 +1,349 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    hash::BuildHasher,
+};
+
+use crate::interpreter::{
+    interpreter::Value,
+    map::{HashKey, ValueMap},
+};
+
+/// A value that was not of the Rust type it was converted to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+    pub expected: String,
+    pub found: String,
+}
+
+impl ConversionError {
+    pub fn new<T: FromValue>(found: &Value) -> ConversionError {
+        ConversionError {
+            expected: T::expected(),
+            found: found.type_name().to_string(),
+        }
+    }
+
+    /// Reports a bad element as a bad container, so the message names the
+    /// type that was asked for.
+    fn inside<T: FromValue>(self, container: &str) -> ConversionError {
+        ConversionError {
+            expected: T::expected(),
+            found: format!("{} containing {}", container, self.found),
+        }
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected {} but got {}.", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// A Rust type that can be handed to scripts as a taco value.
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+/// A Rust type that can be read back out of a taco value.
+pub trait FromValue: Sized {
+    fn from_value(value: Value) -> Result<Self, ConversionError>;
+
+    /// The taco type this accepts, as error messages name it.
+    fn expected() -> String;
+}
+
+impl IntoValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        Ok(value)
+    }
+
+    fn expected() -> String {
+        String::from("any value")
+    }
+}
+
+impl IntoValue for () {
+    fn into_value(self) -> Value {
+        Value::Nil
+    }
+}
+
+impl IntoValue for i64 {
+    fn into_value(self) -> Value {
+        Value::Integer(self)
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Integer(integer) => Ok(integer),
+            other => Err(ConversionError::new::<i64>(&other)),
+        }
+    }
+
+    fn expected() -> String {
+        String::from("integer")
+    }
+}
+
+/// The narrower integer types are checked against their range, so a
+/// script can't pass 300 where a `u8` is wanted.
+macro_rules! integer_conversions {
+    ($($type:ty),*) => {
+        $(
+            impl FromValue for $type {
+                fn from_value(value: Value) -> Result<Self, ConversionError> {
+                    match value {
+                        Value::Integer(integer) => <$type>::try_from(integer).map_err(|_| ConversionError {
+                            expected: Self::expected(),
+                            found: integer.to_string(),
+                        }),
+                        other => Err(ConversionError::new::<$type>(&other)),
+                    }
+                }
+
+                fn expected() -> String {
+                    format!("integer from {} to {}", <$type>::MIN, <$type>::MAX)
+                }
+            }
+        )*
+    };
+}
+
+integer_conversions!(i8, i16, i32, u8, u16, u32, u64, usize);
+
+/// Only the types that always fit in an `i64` convert back.
+macro_rules! lossless_integers {
+    ($($type:ty),*) => {
+        $(
+            impl IntoValue for $type {
+                fn into_value(self) -> Value {
+                    Value::Integer(i64::from(self))
+                }
+            }
+        )*
+    };
+}
+
+lossless_integers!(i8, i16, i32, u8, u16, u32);
+
+impl IntoValue for f64 {
+    fn into_value(self) -> Value {
+        Value::Float(self)
+    }
+}
+
+impl FromValue for f64 {
+    /// Integers are accepted too, since every script writes `2` for `2.0`.
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Float(float) => Ok(float),
+            Value::Integer(integer) => Ok(integer as f64),
+            other => Err(ConversionError::new::<f64>(&other)),
+        }
+    }
+
+    fn expected() -> String {
+        String::from("float")
+    }
+}
+
+impl IntoValue for bool {
+    fn into_value(self) -> Value {
+        Value::Boolean(self)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Boolean(boolean) => Ok(boolean),
+            other => Err(ConversionError::new::<bool>(&other)),
+        }
+    }
+
+    fn expected() -> String {
+        String::from("boolean")
+    }
+}
+
+impl IntoValue for String {
+    fn into_value(self) -> Value {
+        Value::String(self)
+    }
+}
+
+impl IntoValue for &str {
+    fn into_value(self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::String(string) => Ok(string),
+            other => Err(ConversionError::new::<String>(&other)),
+        }
+    }
+
+    fn expected() -> String {
+        String::from("string")
+    }
+}
+
+/// `None` is nil.
+impl<T: IntoValue> IntoValue for Option<T> {
+    fn into_value(self) -> Value {
+        match self {
+            Some(value) => value.into_value(),
+            None => Value::Nil,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Nil => Ok(None),
+            // Report the option as a whole, not just the type it wraps.
+            other => T::from_value(other).map(Some).map_err(|error| ConversionError {
+                expected: Self::expected(),
+                found: error.found,
+            }),
+        }
+    }
+
+    fn expected() -> String {
+        format!("{} or nil", T::expected())
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::list(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::List(list) => list
+                .borrow()
+                .iter()
+                .cloned()
+                .map(T::from_value)
+                .collect::<Result<_, _>>()
+                .map_err(|error| error.inside::<Vec<T>>("list")),
+            other => Err(ConversionError::new::<Vec<T>>(&other)),
+        }
+    }
+
+    fn expected() -> String {
+        format!("list of {}", T::expected())
+    }
+}
+
+impl<T: IntoValue, S: BuildHasher> IntoValue for HashMap<String, T, S> {
+    fn into_value(self) -> Value {
+        let mut map = ValueMap::new();
+        for (key, value) in self {
+            map.insert(HashKey::String(key), value.into_value());
+        }
+
+        Value::map(map)
+    }
+}
+
+impl<T: FromValue, S: BuildHasher + Default> FromValue for HashMap<String, T, S> {
+    /// Every key must be a string.
+    fn from_value(value: Value) -> Result<Self, ConversionError> {
+        let map = match value {
+            Value::Map(map) => map,
+            other => return Err(ConversionError::new::<HashMap<String, T, S>>(&other)),
+        };
+
+        let map = map.borrow();
+        let mut result = HashMap::with_capacity_and_hasher(map.len(), S::default());
+        for (key, value) in map.iter() {
+            let key = match key {
+                HashKey::String(key) => key.clone(),
+                other => {
+                    return Err(ConversionError {
+                        expected: Self::expected(),
+                        found: format!("map with {} key", other.to_value().type_name()),
+                    })
+                }
+            };
+
+            let value = match T::from_value(value.clone()) {
+                Ok(value) => value,
+                Err(error) => return Err(error.inside::<Self>("map")),
+            };
+
+            result.insert(key, value);
+        }
+
+        Ok(result)
+    }
+
+    fn expected() -> String {
+        format!("map of {}", T::expected())
+    }
+}
+
+macro_rules! value_conversions {
+    ($($type:ty),*) => {
+        $(
+            impl From<$type> for Value {
+                fn from(value: $type) -> Value {
+                    value.into_value()
+                }
+            }
+
+            impl TryFrom<Value> for $type {
+                type Error = ConversionError;
+
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    <$type>::from_value(value)
+                }
+            }
+        )*
+    };
+}
+
+value_conversions!(i64, f64, bool, String);
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        value.into_value()
+    }
+}
+
+impl<T: IntoValue> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Value {
+        value.into_value()
+    }
+}
+
+impl<T: IntoValue> From<Vec<T>> for Value {
+    fn from(value: Vec<T>) -> Value {
+        value.into_value()
+    }
+}
+
+impl<T: IntoValue> From<HashMap<String, T>> for Value {
+    fn from(value: HashMap<String, T>) -> Value {
+        value.into_value()
+    }
+}
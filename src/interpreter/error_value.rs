@@ -0,0 +1,23 @@
+/// A runtime error caught by a `catch` clause, as seen by scripts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorValue {
+    message: String,
+    line: u32,
+}
+
+impl ErrorValue {
+    pub fn new(message: &str, line: u32) -> ErrorValue {
+        ErrorValue {
+            message: message.to_string(),
+            line,
+        }
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn get_line(&self) -> u32 {
+        self.line
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter};
+
+/// One entry of a traceback: a function and the line it was executing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+    pub function: String,
+    pub file: Option<String>,
+    pub line: u32,
+}
+
+impl Display for StackFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "at {} ({}:{})", self.function, file, self.line),
+            None => write!(f, "at {} (line {})", self.function, self.line),
+        }
+    }
+}
+
+/// A call the interpreter is currently executing. `call_line` is the line in
+/// the caller the call was made from, which is the line its caller's frame
+/// shows in a traceback.
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+    pub function: String,
+    pub file: Option<String>,
+    pub call_line: u32,
+}
+
+/// Builds a traceback, innermost frame first, for an error raised at `line`
+/// inside the innermost of `calls`. The outermost frame is the script.
+pub fn trace(calls: &[CallFrame], line: u32, script: Option<&str>) -> Vec<StackFrame> {
+    let mut frames = Vec::<StackFrame>::new();
+    let mut line = line;
+
+    for call in calls.iter().rev() {
+        frames.push(StackFrame {
+            function: call.function.clone(),
+            file: call.file.clone(),
+            line,
+        });
+        line = call.call_line;
+    }
+
+    frames.push(StackFrame {
+        function: String::from("<script>"),
+        file: script.map(String::from),
+        line,
+    });
+
+    frames
+}
@@ -0,0 +1,71 @@
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    rc::Rc,
+};
+
+use crate::{
+    environment::environment::Environment, syntax::function::FunctionStatement, vm::chunk::Chunk,
+};
+
+/// A function declared in taco, closed over the scope it was declared in.
+pub struct Function {
+    declaration: FunctionStatement,
+    closure: Rc<RefCell<Environment>>,
+    // The module the function was declared in, for tracebacks and imports.
+    file: Option<String>,
+    // The compiled body, for functions declared in code run by the VM.
+    chunk: Option<Rc<Chunk>>,
+}
+
+impl Function {
+    pub fn new(
+        declaration: FunctionStatement,
+        closure: Rc<RefCell<Environment>>,
+        file: Option<String>,
+        chunk: Option<Rc<Chunk>>,
+    ) -> Function {
+        Function {
+            declaration,
+            closure,
+            file,
+            chunk,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.declaration.name.get_lexeme()
+    }
+
+    pub fn get_declaration(&self) -> &FunctionStatement {
+        &self.declaration
+    }
+
+    pub fn get_closure(&self) -> &Rc<RefCell<Environment>> {
+        &self.closure
+    }
+
+    pub fn get_file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    pub fn get_chunk(&self) -> Option<&Chunk> {
+        self.chunk.as_deref()
+    }
+
+    pub fn arity(&self) -> usize {
+        self.declaration.params.len()
+    }
+}
+
+impl Debug for Function {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<fn {}>", self.get_name())
+    }
+}
+
+impl PartialEq for Function {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    environment::environment::Environment,
+    interpreter::{function::Function, interpreter::Value, map::ValueMap},
+};
+
+// Collections are not worth running until at least this many objects have
+// been tracked since the last one.
+const MIN_THRESHOLD: usize = 1000;
+
+/// A shared value that can take part in a reference cycle.
+enum Object {
+    List(Weak<RefCell<Vec<Value>>>),
+    Map(Weak<RefCell<ValueMap>>),
+    Environment(Weak<RefCell<Environment>>),
+    Function(Weak<Function>),
+}
+
+enum Live {
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<ValueMap>>),
+    Environment(Rc<RefCell<Environment>>),
+    Function(Rc<Function>),
+}
+
+thread_local! {
+    // Keyed by address, so tracking the same object twice is harmless.
+    static OBJECTS: RefCell<HashMap<usize, Object>> = RefCell::new(HashMap::new());
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+    static THRESHOLD: Cell<usize> = const { Cell::new(MIN_THRESHOLD) };
+}
+
+fn address<T>(rc: &Rc<T>) -> usize {
+    Rc::as_ptr(rc) as *const () as usize
+}
+
+fn track(id: usize, object: Object) {
+    OBJECTS.with(|objects| objects.borrow_mut().insert(id, object));
+    ALLOCATED.with(|allocated| allocated.set(allocated.get() + 1));
+}
+
+pub fn track_list(list: &Rc<RefCell<Vec<Value>>>) {
+    track(address(list), Object::List(Rc::downgrade(list)));
+}
+
+pub fn track_map(map: &Rc<RefCell<ValueMap>>) {
+    track(address(map), Object::Map(Rc::downgrade(map)));
+}
+
+/// Tracks a function and the scopes it closes over. Those scopes are the
+/// only environments a cycle can pass through, so other scopes, such as
+/// the one each call runs in, never need tracking.
+pub fn track_function(function: &Rc<Function>) {
+    track(address(function), Object::Function(Rc::downgrade(function)));
+
+    let mut scope = Some(function.get_closure().clone());
+    while let Some(environment) = scope {
+        track(address(&environment), Object::Environment(Rc::downgrade(&environment)));
+        scope = environment.borrow().get_enclosing();
+    }
+}
+
+/// Whether enough has been allocated since the last collection to make
+/// another one worthwhile.
+pub fn should_collect() -> bool {
+    ALLOCATED.with(|allocated| allocated.get()) >= THRESHOLD.with(|threshold| threshold.get())
+}
+
+/// How many tracked objects are still alive, including unreachable cycles
+/// that have not been collected yet.
+pub fn tracked() -> usize {
+    OBJECTS.with(|objects| {
+        objects
+            .borrow()
+            .values()
+            .filter(|object| match object {
+                Object::List(list) => list.strong_count() > 0,
+                Object::Map(map) => map.strong_count() > 0,
+                Object::Environment(environment) => environment.strong_count() > 0,
+                Object::Function(function) => function.strong_count() > 0,
+            })
+            .count()
+    })
+}
+
+/// Frees objects that are only kept alive by references from each other.
+///
+/// An object whose reference count is higher than the number of references
+/// to it from other tracked objects is referenced from somewhere else, such
+/// as a variable the host holds or a value on the interpreter's stack, and
+/// is a root. Everything reachable from a root is kept. The contents of the
+/// rest are cleared, which breaks their cycles and lets them be dropped.
+///
+/// Objects that are borrowed while this runs are treated as roots, so it is
+/// safe to call at any point. Returns how many objects were freed.
+pub fn collect() -> usize {
+    let live = OBJECTS.with(|objects| {
+        let mut objects = objects.borrow_mut();
+        let mut live = HashMap::<usize, Live>::new();
+
+        objects.retain(|id, object| {
+            let upgraded = match object {
+                Object::List(list) => list.upgrade().map(Live::List),
+                Object::Map(map) => map.upgrade().map(Live::Map),
+                Object::Environment(environment) => environment.upgrade().map(Live::Environment),
+                Object::Function(function) => function.upgrade().map(Live::Function),
+            };
+
+            match upgraded {
+                Some(object) => {
+                    live.insert(*id, object);
+                    true
+                }
+                None => false,
+            }
+        });
+
+        live
+    });
+
+    // References to each object from other tracked objects, and the tracked
+    // objects each one refers to.
+    let mut internal = HashMap::<usize, usize>::new();
+    let mut children = HashMap::<usize, Vec<usize>>::new();
+    let mut roots = Vec::<usize>::new();
+
+    for (id, object) in &live {
+        match references(object) {
+            Some(references) => {
+                let references = references
+                    .into_iter()
+                    .filter(|child| live.contains_key(child))
+                    .collect::<Vec<usize>>();
+
+                for child in &references {
+                    *internal.entry(*child).or_insert(0) += 1;
+                }
+                children.insert(*id, references);
+            }
+            None => roots.push(*id),
+        }
+    }
+
+    for (id, object) in &live {
+        // One of the strong references is the `live` map's own.
+        let count = match object {
+            Live::List(list) => Rc::strong_count(list),
+            Live::Map(map) => Rc::strong_count(map),
+            Live::Environment(environment) => Rc::strong_count(environment),
+            Live::Function(function) => Rc::strong_count(function),
+        } - 1;
+
+        if count > internal.get(id).copied().unwrap_or(0) {
+            roots.push(*id);
+        }
+    }
+
+    let mut reachable = HashSet::<usize>::new();
+    while let Some(id) = roots.pop() {
+        if reachable.insert(id) {
+            if let Some(references) = children.get(&id) {
+                roots.extend(references.iter().copied());
+            }
+        }
+    }
+
+    let mut freed = 0;
+    for (id, object) in &live {
+        if reachable.contains(id) {
+            continue;
+        }
+
+        freed += 1;
+        match object {
+            Live::List(list) => list.borrow_mut().clear(),
+            Live::Map(map) => *map.borrow_mut() = ValueMap::new(),
+            Live::Environment(environment) => environment.borrow_mut().clear(),
+            Live::Function(_) => {}
+        }
+    }
+
+    let survivors = live.len() - freed;
+    drop(live);
+
+    OBJECTS.with(|objects| {
+        objects
+            .borrow_mut()
+            .retain(|id, _| reachable.contains(id))
+    });
+    ALLOCATED.with(|allocated| allocated.set(0));
+    THRESHOLD.with(|threshold| threshold.set(MIN_THRESHOLD.max(survivors * 2)));
+
+    freed
+}
+
+/// The addresses of the shared values `object` refers to, or `None` when it
+/// is borrowed and cannot be inspected.
+fn references(object: &Live) -> Option<Vec<usize>> {
+    let mut references = Vec::new();
+
+    match object {
+        Live::List(list) => {
+            for value in list.try_borrow().ok()?.iter() {
+                reference(value, &mut references);
+            }
+        }
+        Live::Map(map) => {
+            for (_, value) in map.try_borrow().ok()?.iter() {
+                reference(value, &mut references);
+            }
+        }
+        Live::Environment(environment) => {
+            let environment = environment.try_borrow().ok()?;
+            for value in environment.values() {
+                reference(value, &mut references);
+            }
+            if let Some(enclosing) = environment.get_enclosing() {
+                references.push(address(&enclosing));
+            }
+        }
+        Live::Function(function) => references.push(address(function.get_closure())),
+    }
+
+    Some(references)
+}
+
+fn reference(value: &Value, references: &mut Vec<usize>) {
+    match value {
+        Value::List(list) => references.push(address(list)),
+        Value::Map(map) => references.push(address(map)),
+        Value::Function(function) => references.push(address(function)),
+        _ => {}
+    }
+}
@@ -1,23 +1,66 @@
-use std::ops::Deref;
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 use crate::{
     core::{expression::Expr, literal::Literal},
     environment::environment::Environment,
-    error::{interpreter::RuntimeError, parser::ParserError},
-    lang::taco::Taco,
-    syntax::statement::Statement,
-    token::tokens::TokenType,
+    error::{
+        interpreter::{ErrorKind, RuntimeError},
+        parser::{scan_error, ParserError},
+    },
+    interpreter::{
+        error_value::ErrorValue,
+        frame::{self, CallFrame, StackFrame},
+        function::Function,
+        heap,
+        limits::{Limits, Meter, Usage},
+        output::Output,
+        map::{HashKey, ValueMap},
+        module::ModuleLoader,
+        operators,
+        native::{Arity, HostFunction, NativeFunction},
+    },
+    optimizer::optimizer,
+    parser::parser::Parser,
+    stdlib,
+    syntax::{
+        function::FunctionStatement, import::ImportStatement, r#return::ReturnStatement,
+        statement::Statement,
+    },
+    token::{
+        scanner::Scanner,
+        symbol::Symbol,
+        tokens::{Token, TokenType},
+    },
+    util::{normalize_index, slice_bounds},
+    vm::{chunk::Chunk, compiler::Compiler},
 };
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Value {
     Integer(i64),
     Float(f64),
     String(String),
     Boolean(bool),
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<ValueMap>>),
+    Function(Rc<Function>),
+    NativeFunction(NativeFunction),
+    Error(Rc<ErrorValue>),
     Nil,
 }
 
+/// Lists and maps compare by contents, and may contain themselves.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        operators::equal(self, other)
+    }
+}
+
 impl From<Literal> for Value {
     fn from(literal: Literal) -> Self {
         match literal {
@@ -31,6 +74,20 @@ impl From<Literal> for Value {
 }
 
 impl Value {
+    /// A new list, tracked by the cycle collector.
+    pub fn list(elements: Vec<Value>) -> Value {
+        let list = Rc::new(RefCell::new(elements));
+        heap::track_list(&list);
+        Value::List(list)
+    }
+
+    /// A new map, tracked by the cycle collector.
+    pub fn map(map: ValueMap) -> Value {
+        let map = Rc::new(RefCell::new(map));
+        heap::track_map(&map);
+        Value::Map(map)
+    }
+
     pub fn is_truthy(&self) -> bool {
         match self {
             Value::Nil => false,
@@ -38,22 +95,361 @@ impl Value {
             _ => true,
         }
     }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::String(_) => "string",
+            Value::Boolean(_) => "boolean",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::Function(_) | Value::NativeFunction(_) => "function",
+            Value::Error(_) => "error",
+            Value::Nil => "nil",
+        }
+    }
+}
+
+/// How deeply calls may nest before the interpreter gives up with a stack
+/// overflow. The native stack grows on demand, so this holds on any thread.
+pub const DEFAULT_MAX_DEPTH: usize = 1000;
+
+// When less than `STACK_RED_ZONE` of native stack is left at a call, the body
+// runs on a fresh `STACK_SEGMENT`. The red zone covers one call's worth of
+// frames in an unoptimised build, with room to spare.
+const STACK_RED_ZONE: usize = 256 * 1024;
+const STACK_SEGMENT: usize = 4 * 1024 * 1024;
+
+/// Which strategy runs parsed code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Walks the syntax tree directly.
+    Tree,
+    /// Compiles to bytecode and runs it on a stack machine.
+    Vm,
+}
+
+impl Backend {
+    pub fn from_name(name: &str) -> Option<Backend> {
+        match name {
+            "tree" => Some(Backend::Tree),
+            "vm" => Some(Backend::Vm),
+            _ => None,
+        }
+    }
+}
+
+/// How control leaves a statement.
+pub enum Flow {
+    Next,
+    Return(Value),
 }
 
 #[derive(Debug, Clone)]
 pub struct Interpreter {
     had_error: bool,
-    had_runtime_error: bool,
+    // Builtins, visible to scripts and modules alike.
+    globals: Rc<RefCell<Environment>>,
+    // The top-level scope of scripts, kept across calls to `interpret` so
+    // that each REPL line sees what earlier ones defined.
+    environment: Rc<RefCell<Environment>>,
+    modules: ModuleLoader,
+    // The file being executed, used to resolve relative imports.
+    current_file: Option<PathBuf>,
+    // Names exported so far by the module being executed.
+    exports: Vec<Token>,
+    // The script top-level code comes from, shown at the bottom of tracebacks.
+    script_file: Option<String>,
+    // Function calls and module imports currently executing, innermost last.
+    calls: Vec<CallFrame>,
+    max_depth: usize,
+    backend: Backend,
+    // Whether programs pass through the optimizer before they run.
+    optimize: bool,
+    meter: Meter,
+    // How many runs the host has started that are still going. Host
+    // functions can call back in, and only the outermost run starts metering.
+    entered: usize,
+    output: Output,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
 }
 
 impl Interpreter {
-    pub fn new(instance: &Taco) -> Interpreter {
-        Interpreter {
-            had_error: instance.has_error,
-            had_runtime_error: instance.has_runtime_error,
+    /// An interpreter whose scripts print to the process's stdout and
+    /// stderr.
+    pub fn new() -> Interpreter {
+        Interpreter::with_output(Output::default())
+    }
+
+    /// An interpreter whose scripts write to the given sinks instead, for
+    /// hosts and tests that capture output.
+    pub fn with_output(output: Output) -> Interpreter {
+        let globals = Rc::new(RefCell::new(Environment::new()));
+        let mut interpreter = Interpreter {
+            had_error: false,
+            globals: globals.clone(),
+            environment: Rc::new(RefCell::new(Environment::new_enclosing(globals))),
+            modules: ModuleLoader::new(),
+            current_file: None,
+            exports: Vec::new(),
+            script_file: None,
+            calls: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            backend: Backend::Tree,
+            optimize: false,
+            meter: Meter::default(),
+            entered: 0,
+            output,
+        };
+
+        stdlib::prelude::register(&mut interpreter);
+        stdlib::math::register(&mut interpreter);
+        stdlib::io::register(&mut interpreter);
+        stdlib::fs::register(&mut interpreter);
+        #[cfg(feature = "serde")]
+        stdlib::json::register(&mut interpreter);
+
+        interpreter
+    }
+
+    /// Sets the script that top-level code comes from, so its imports resolve
+    /// relative to it and importing it back is reported as a cycle.
+    pub fn set_main_file(&mut self, path: &Path) {
+        if let Ok(canonical) = fs::canonicalize(path) {
+            self.modules.begin(canonical);
+        }
+
+        self.current_file = Some(path.to_path_buf());
+        self.script_file = Some(path.display().to_string());
+    }
+
+    /// Runs `run` with `path` as the main file, as `set_main_file` sets it,
+    /// then puts back the previous main file.
+    pub fn with_main_file<T>(&mut self, path: &Path, run: impl FnOnce(&mut Interpreter) -> T) -> T {
+        let previous_file = self.current_file.clone();
+        let previous_script = self.script_file.take();
+        let loading = fs::canonicalize(path).is_ok();
+
+        self.set_main_file(path);
+        let result = run(self);
+
+        if loading {
+            self.modules.finish(None);
+        }
+        self.current_file = previous_file;
+        self.script_file = previous_script;
+
+        result
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Limits how deeply function calls and imports may nest. Exceeding the
+    /// limit raises a "Stack overflow." error at the offending call. The
+    /// native stack grows as needed, so large limits are safe on any thread;
+    /// they only cost memory.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    pub fn get_backend(&self) -> Backend {
+        self.backend
+    }
+
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend;
+    }
+
+    pub fn get_optimize(&self) -> bool {
+        self.optimize
+    }
+
+    /// Enables constant folding and dead branch removal for code run from
+    /// now on, including modules imported by it.
+    pub fn set_optimize(&mut self, optimize: bool) {
+        self.optimize = optimize;
+    }
+
+    /// Where scripts' output goes, for natives that write to it.
+    pub fn get_output(&self) -> &Output {
+        &self.output
+    }
+
+    pub fn get_limits(&self) -> &Limits {
+        self.meter.get_limits()
+    }
+
+    /// Caps what each script run from now on may do. Exceeding a limit
+    /// raises an error of the matching `ErrorKind`, which scripts cannot
+    /// catch.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.meter.set_limits(limits);
+    }
+
+    /// What the last script used, or the running one has used so far.
+    pub fn get_usage(&self) -> &Usage {
+        self.meter.get_usage()
+    }
+
+    /// Counts `bytes` of output against the output limit. Natives that
+    /// write somewhere the script's user can see call this first.
+    pub fn charge_output(&mut self, bytes: usize) -> Result<(), String> {
+        match self.meter.output(bytes) {
+            Ok(()) => Ok(()),
+            Err(kind) => Err(self.meter.describe(kind)),
+        }
+    }
+
+    /// The error for an exceeded limit. Limits are not tied to a token, so
+    /// the error carries a placeholder at the line being run.
+    fn limit_error(&self, kind: ErrorKind, line: Option<u32>) -> RuntimeError {
+        let token = Token::new(TokenType::Eof, String::new(), String::new(), line.unwrap_or(0));
+        RuntimeError::new(token, &self.meter.describe(kind)).with_kind(kind)
+    }
+
+    pub(crate) fn step(&mut self, line: impl FnOnce() -> Option<u32>) -> Result<(), RuntimeError> {
+        match self.meter.step() {
+            Ok(()) => Ok(()),
+            Err(kind) => Err(self.limit_error(kind, line())),
+        }
+    }
+
+    /// Charges a newly created value against the allocation limit.
+    pub(crate) fn allocate(&mut self, value: &Value, line: Option<u32>) -> Result<(), RuntimeError> {
+        match self.meter.allocate(value) {
+            Ok(()) => Ok(()),
+            Err(kind) => Err(self.limit_error(kind, line)),
+        }
+    }
+
+    /// Charges for the entry an index assignment may add to a map.
+    pub(crate) fn allocate_entry(&mut self, object: &Value, line: u32) -> Result<(), RuntimeError> {
+        if let Value::Map(_) = object {
+            if let Err(kind) = self.meter.allocate_bytes(Meter::map_entry()) {
+                return Err(self.limit_error(kind, Some(line)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints a line of script output.
+    pub(crate) fn print(&mut self, value: Value, line: Option<u32>) -> Result<(), RuntimeError> {
+        let mut text = self.stringify(value);
+        text.push('\n');
+
+        if let Err(kind) = self.meter.output(text.len()) {
+            return Err(self.limit_error(kind, line));
+        }
+
+        match self.output.write_stdout(&text) {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                let token = Token::new(TokenType::Print, String::from("print"), String::new(), line.unwrap_or(0));
+                Err(RuntimeError::new(token, &format!("Could not write output: {}", err)))
+            }
         }
     }
 
+    /// Frees lists, maps and functions that are only kept alive by
+    /// reference cycles, returning how many objects were freed. This also
+    /// happens automatically as scripts allocate.
+    pub fn collect_garbage(&mut self) -> usize {
+        heap::collect()
+    }
+
+    /// Collects cycles if enough has been allocated since the last time.
+    /// Called where scripts can loop: after calls and between iterations.
+    pub(crate) fn maybe_collect(&mut self) {
+        if heap::should_collect() {
+            heap::collect();
+        }
+    }
+
+    /// The calls currently executing, innermost first. Each frame's line is
+    /// the line that frame is currently executing, as far as it is known
+    /// from the call sites above it.
+    pub fn call_stack(&self, line: u32) -> Vec<StackFrame> {
+        frame::trace(&self.calls, line, self.script_file.as_deref())
+    }
+
+    /// Defines a builtin, which modules can see as well as scripts.
+    pub fn define_global(&mut self, name: &str, value: Value) {
+        self.globals.borrow_mut().define(Symbol::intern(name), value);
+    }
+
+    /// Binds `name` in the top-level scope scripts run in, as a top-level
+    /// `let` would.
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        self.environment.borrow_mut().define(Symbol::intern(name), value);
+    }
+
+    /// The value `name` has at the top level of scripts, which is either
+    /// something a script or `set_global` defined, or a builtin.
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.environment.borrow().lookup(Symbol::intern(name))
+    }
+
+    /// Forgets everything defined at the top level, leaving the builtins.
+    pub fn reset_globals(&mut self) {
+        self.environment = Rc::new(RefCell::new(Environment::new_enclosing(self.globals.clone())));
+        self.collect_garbage();
+    }
+
+    /// The names defined at the top level, not counting builtins, with
+    /// their values, sorted by name.
+    pub fn top_level_bindings(&self) -> Vec<(String, Value)> {
+        let mut bindings = self
+            .environment
+            .borrow()
+            .bindings()
+            .map(|(name, value)| (name.name().to_string(), value.clone()))
+            .collect::<Vec<_>>();
+
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        bindings
+    }
+
+    /// Every name scripts can use at the top level, builtins included, in
+    /// no particular order.
+    pub fn global_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for scope in [&self.environment, &self.globals] {
+            names.extend(scope.borrow().bindings().map(|(name, _)| name.name().to_string()));
+        }
+
+        names
+    }
+
+    /// Defines a host function in the global environment, shadowing any
+    /// existing global of the same name.
+    pub fn define_native<F>(&mut self, name: &str, arity: Arity, function: F)
+    where
+        F: Fn(&mut Interpreter, Vec<Value>) -> Result<Value, String> + 'static,
+    {
+        let native = NativeFunction::new(name, arity, function);
+        self.define_global(name, Value::NativeFunction(native));
+    }
+
+    /// Defines a host function whose arguments are converted to Rust types,
+    /// such as `|a: i64, b: i64| a + b`. Calls with the wrong number or
+    /// types of arguments raise runtime errors.
+    pub fn define_fn<F, Args>(&mut self, name: &str, function: F)
+    where
+        F: HostFunction<Args> + 'static,
+    {
+        let native = NativeFunction::from_host(name, function);
+        self.define_global(name, Value::NativeFunction(native));
+    }
+
     // pub fn run_file(&mut self, path: &str) {
     //     let source = std::fs::read_to_string(path).unwrap();
     //     self.run(source);
@@ -87,57 +483,487 @@ impl Interpreter {
     // }
 
     pub fn stringify(&self, value: Value) -> String {
+        self.format(value, false, &mut Vec::new())
+    }
+
+    /// Like `stringify`, but quotes strings so they can be told apart from
+    /// other values when nested inside a list or map.
+    pub(crate) fn repr(&self, value: Value) -> String {
+        self.format(value, true, &mut Vec::new())
+    }
+
+    // `open` holds the lists and maps being formatted further up, so one
+    // that contains itself prints as `[...]` or `{...}` instead of recursing.
+    fn format(&self, value: Value, quoted: bool, open: &mut Vec<*const ()>) -> String {
         match value {
             Value::Integer(i) => i.to_string(),
             Value::Float(f) => f.to_string(),
+            Value::String(s) if quoted => format!("{:?}", s),
             Value::String(s) => s,
             Value::Boolean(b) => b.to_string(),
+            Value::List(list) => {
+                let id = Rc::as_ptr(&list) as *const ();
+                if open.contains(&id) {
+                    return "[...]".to_string();
+                }
+
+                open.push(id);
+                let elements = list
+                    .borrow()
+                    .iter()
+                    .map(|element| self.format(element.clone(), true, open))
+                    .collect::<Vec<String>>();
+                open.pop();
+
+                format!("[{}]", elements.join(", "))
+            }
+            Value::Map(map) => {
+                let id = Rc::as_ptr(&map) as *const ();
+                if open.contains(&id) {
+                    return "{...}".to_string();
+                }
+
+                open.push(id);
+                let entries = map
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| {
+                        format!(
+                            "{}: {}",
+                            self.format(key.to_value(), true, open),
+                            self.format(value.clone(), true, open)
+                        )
+                    })
+                    .collect::<Vec<String>>();
+                open.pop();
+
+                format!("{{{}}}", entries.join(", "))
+            }
+            Value::Function(function) => format!("<fn {}>", function.get_name()),
+            Value::NativeFunction(native) => format!("<native fn {}>", native.get_name()),
+            Value::Error(error) => format!("Error: {}", error.get_message()),
             Value::Nil => "nil".to_string(),
         }
     }
 
-    pub fn interpret(&self, ast: Result<Vec<Statement>, ParserError>) -> Result<(), RuntimeError> {
+    pub fn interpret(&mut self, ast: Result<Vec<Statement>, ParserError>) -> Result<(), RuntimeError> {
         if self.had_error {
             return Ok(());
         }
 
         let statements = match ast {
             Ok(expr) => expr,
-            Err(err) => {
+            Err(_) => {
                 // taco.set_error(true);
                 return Ok(());
             }
         };
 
-        let mut environment = Environment::new();
+        self.execute_program(statements)?;
+        Ok(())
+    }
 
-        for statement in statements {
-            self.execute(&mut environment, statement);
+    /// Runs a program like `interpret`, but returns the value of a final
+    /// expression statement, or nil if the program does not end with one.
+    pub fn interpret_value(&mut self, mut statements: Vec<Statement>) -> Result<Value, RuntimeError> {
+        // Running the last expression as a `return` hands its value back
+        // from whichever backend runs the program.
+        if let Some(Statement::ExpressionStatement(_)) = statements.last() {
+            if let Some(Statement::ExpressionStatement(last)) = statements.pop() {
+                let line = last.expression.line().unwrap_or(0);
+                let keyword = Token::new(TokenType::Return, String::from("return"), String::new(), line);
+                statements.push(Statement::ReturnStatement(ReturnStatement::new(
+                    keyword,
+                    Some(last.expression),
+                )));
+            }
         }
 
-        Ok(())
+        match self.execute_program(statements)? {
+            Flow::Return(value) => Ok(value),
+            Flow::Next => Ok(Value::Nil),
+        }
+    }
+
+    /// Calls a taco function, or a native one, from the host.
+    pub fn call_value(&mut self, callee: Value, arguments: Vec<Value>) -> Result<Value, RuntimeError> {
+        let paren = Token::new(TokenType::RightParen, String::from(")"), String::new(), 0);
+
+        self.enter();
+        let result = self.call(&paren, callee, arguments);
+        self.entered -= 1;
+        self.maybe_collect();
+
+        result.map_err(|error| self.traced(error))
+    }
+
+    /// Runs top-level statements in the scripts' top-level scope.
+    fn execute_program(&mut self, statements: Vec<Statement>) -> Result<Flow, RuntimeError> {
+        let environment = self.environment.clone();
+        let statements = self.prepare(statements);
+        self.enter();
+        let result = self.run(&environment, &statements);
+        self.entered -= 1;
+        self.maybe_collect();
+
+        result.map_err(|error| self.traced(error))
+    }
+
+    /// Notes that the host has started a run, and starts metering it unless
+    /// it was started from inside another, which it then shares limits with.
+    fn enter(&mut self) {
+        if self.entered == 0 {
+            self.meter.start();
+        }
+        self.entered += 1;
+    }
+
+    /// Gives an error that left the interpreter without a traceback one
+    /// for where it was raised.
+    fn traced(&self, mut error: RuntimeError) -> RuntimeError {
+        if error.trace.is_empty() {
+            error.trace = self.call_stack(error.token.get_line()).into();
+        }
+
+        error
     }
 
-    fn execute(&self, environment: &mut Environment, statement: Statement) {
+    /// Applies the optimizer when it is enabled.
+    fn prepare(&self, statements: Vec<Statement>) -> Vec<Statement> {
+        match self.optimize {
+            true => optimizer::optimize(statements),
+            false => statements,
+        }
+    }
+
+    /// Runs top-level statements with the configured backend.
+    fn run(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        statements: &[Statement],
+    ) -> Result<Flow, RuntimeError> {
+        match self.backend {
+            Backend::Tree => self.execute_statements(environment, statements),
+            Backend::Vm => {
+                let chunk = Compiler::compile(statements);
+                self.run_chunk(environment, &chunk)
+            }
+        }
+    }
+
+    fn execute(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        statement: &Statement,
+    ) -> Result<Flow, RuntimeError> {
+        self.step(|| statement.line())?;
+
         match statement {
             Statement::LetStatement(expr) => {
-                let initializer = match expr.initializer {
+                let initializer = match &expr.initializer {
                     Expr::Literal(Literal::Nil) => Value::Nil,
-                    _ => self.evaluate(environment, expr.initializer),
+                    initializer => self.evaluate(environment, initializer)?,
                 };
 
-                environment.define(expr.name.get_lexeme().to_string(), initializer);
+                environment
+                    .borrow_mut()
+                    .define(expr.name.get_symbol(), initializer);
             }
             Statement::ExpressionStatement(expr) => {
-                let value = self.evaluate(environment, expr.expression);
-                println!("{}", self.stringify(value));
+                self.evaluate(environment, &expr.expression)?;
             }
             Statement::PrintStatement(expr) => {
-                let value = self.evaluate(environment, expr.expression);
-                println!("{}", self.stringify(value));
+                let value = self.evaluate(environment, &expr.expression)?;
+                self.print(value, expr.expression.line())?;
+            }
+            Statement::BlockStatement(block) => {
+                return self.execute_block(environment, &block.statements);
+            }
+            Statement::ImportStatement(import) => {
+                self.bind_import(environment, import)?;
+            }
+            Statement::ExportStatement(export) => {
+                let name = match export.declaration.as_ref() {
+                    Statement::LetStatement(declaration) => Some(declaration.name.clone()),
+                    Statement::FunctionStatement(declaration) => Some(declaration.name.clone()),
+                    _ => None,
+                };
+
+                self.execute(environment, &export.declaration)?;
+                self.exports.extend(name);
+            }
+            Statement::ThrowStatement(throw) => {
+                let value = self.evaluate(environment, &throw.value)?;
+                return Err(self.throw(&throw.keyword, value));
+            }
+            Statement::TryStatement(statement) => {
+                let mut result = self.execute_block(environment, &statement.body);
+
+                // Limits stop the script outright.
+                if matches!(&result, Err(error) if error.get_kind().is_limit()) {
+                    return result;
+                }
+
+                if let (Err(error), Some(catch)) = (&result, &statement.catch) {
+                    let scope = Rc::new(RefCell::new(Environment::new_enclosing(
+                        environment.clone(),
+                    )));
+                    if let Some(name) = &catch.name {
+                        scope
+                            .borrow_mut()
+                            .define(name.get_symbol(), error.to_value());
+                    }
+
+                    result = self.execute_block(&scope, &catch.body);
+                }
+
+                // An error or return in the finally block replaces whatever
+                // the rest of the statement was doing.
+                if let Some(finally) = &statement.finally {
+                    if let Flow::Return(value) = self.execute_block(environment, finally)? {
+                        return Ok(Flow::Return(value));
+                    }
+                }
+
+                return result;
+            }
+            Statement::FunctionStatement(declaration) => {
+                self.define_function(environment, declaration.clone(), None);
+            }
+            Statement::IfStatement(statement) => {
+                if self.evaluate(environment, &statement.condition)?.is_truthy() {
+                    return self.execute(environment, &statement.then_branch);
+                } else if let Some(else_branch) = &statement.else_branch {
+                    return self.execute(environment, else_branch);
+                }
+            }
+            Statement::WhileStatement(statement) => {
+                while self.evaluate(environment, &statement.condition)?.is_truthy() {
+                    if let Flow::Return(value) = self.execute(environment, &statement.body)? {
+                        return Ok(Flow::Return(value));
+                    }
+
+                    self.maybe_collect();
+                }
+            }
+            Statement::ReturnStatement(statement) => {
+                let value = match &statement.value {
+                    Some(value) => self.evaluate(environment, value)?,
+                    None => Value::Nil,
+                };
+
+                return Ok(Flow::Return(value));
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    /// Runs `statements` in a new scope nested inside `environment`.
+    fn execute_block(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        statements: &[Statement],
+    ) -> Result<Flow, RuntimeError> {
+        let scope = Rc::new(RefCell::new(Environment::new_enclosing(environment.clone())));
+        self.execute_statements(&scope, statements)
+    }
+
+    fn execute_statements(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        statements: &[Statement],
+    ) -> Result<Flow, RuntimeError> {
+        for statement in statements {
+            if let Flow::Return(value) = self.execute(environment, statement)? {
+                return Ok(Flow::Return(value));
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    /// Binds what an import statement asks for in `environment`: the whole
+    /// module under its alias and/or the individually named exports.
+    pub(crate) fn bind_import(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        import: &ImportStatement,
+    ) -> Result<(), RuntimeError> {
+        let module = self.import_module(import)?;
+
+        if let Some(alias) = &import.alias {
+            environment
+                .borrow_mut()
+                .define(alias.get_symbol(), Value::Map(module.clone()));
+        }
+
+        for name in &import.names {
+            let key = HashKey::String(name.get_lexeme().to_string());
+            let value = match module.borrow().get(&key) {
+                Some(value) => value.clone(),
+                None => {
+                    return Err(RuntimeError::new(
+                        name.clone(),
+                        &format!(
+                            "Module '{}' has no export '{}'.",
+                            import.path.get_literal(),
+                            name.get_lexeme()
+                        ),
+                    ))
+                }
+            };
+
+            environment
+                .borrow_mut()
+                .define(name.get_symbol(), value);
+        }
+
+        Ok(())
+    }
+
+    /// Records that the module being executed exports `name`.
+    pub(crate) fn export(&mut self, name: Token) {
+        self.exports.push(name);
+    }
+
+    /// The error a `throw` statement raises for `value`.
+    pub(crate) fn throw(&self, keyword: &Token, value: Value) -> RuntimeError {
+        let message = match &value {
+            Value::Error(error) => error.get_message().to_string(),
+            other => self.stringify(other.clone()),
+        };
+
+        RuntimeError::thrown(keyword.clone(), value, &message)
+    }
+
+    /// Defines a function closed over `environment`. `chunk` is its compiled
+    /// body when it was declared in code run by the VM.
+    pub(crate) fn define_function(
+        &self,
+        environment: &Rc<RefCell<Environment>>,
+        declaration: FunctionStatement,
+        chunk: Option<Rc<Chunk>>,
+    ) {
+        let name = declaration.name.get_symbol();
+        let file = self.current_file.as_ref().map(|file| file.display().to_string());
+        let function = Rc::new(Function::new(declaration, environment.clone(), file, chunk));
+        heap::track_function(&function);
+
+        environment
+            .borrow_mut()
+            .define(name, Value::Function(function));
+    }
+
+    /// Called as an error leaves the innermost call. The first time, it
+    /// records the calls that were active and the file the error came from.
+    fn unwind(&self, mut error: RuntimeError) -> RuntimeError {
+        if !error.trace.is_empty() {
+            return error;
+        }
+
+        error.trace = self.call_stack(error.token.get_line()).into();
+
+        let file = self.calls.last().and_then(|call| call.file.clone());
+        match file {
+            Some(file) if Some(&file) != self.script_file.as_ref() => error.in_file(&file),
+            _ => error,
+        }
+    }
+
+    /// Loads the module an import refers to, running it in its own global
+    /// environment the first time and returning its cached exports after.
+    fn import_module(&mut self, import: &ImportStatement) -> Result<Rc<RefCell<ValueMap>>, RuntimeError> {
+        let requested = import.path.get_literal();
+        let resolved = ModuleLoader::resolve(self.current_file.as_deref(), requested);
+
+        let path = match fs::canonicalize(&resolved) {
+            Ok(path) => path,
+            Err(err) => {
+                return Err(RuntimeError::new(
+                    import.keyword.clone(),
+                    &format!("Cannot import '{}': {}", requested, err),
+                ))
             }
-            _ => todo!(),
+        };
+
+        if let Some(module) = self.modules.get(&path) {
+            return Ok(module);
+        }
+
+        if let Some(cycle) = self.modules.cycle(&path) {
+            return Err(RuntimeError::new(
+                import.keyword.clone(),
+                &format!("Import cycle detected: {}", cycle),
+            ));
         }
+
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(err) => {
+                return Err(RuntimeError::new(
+                    import.keyword.clone(),
+                    &format!("Cannot import '{}': {}", requested, err),
+                ))
+            }
+        };
+
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens();
+        let parsed = match scanner.errors.first() {
+            Some((line, message)) => Err(scan_error(*line, message)),
+            None => Parser::new(tokens).parse(),
+        };
+
+        let statements = match parsed {
+            Ok(statements) => statements,
+            Err(error) => {
+                return Err(RuntimeError::new(
+                    import.keyword.clone(),
+                    &format!("Cannot import '{}': {}", requested, error),
+                ))
+            }
+        };
+
+        self.modules.begin(path);
+        let previous_file = self.current_file.replace(resolved.clone());
+        let previous_exports = std::mem::take(&mut self.exports);
+        self.calls.push(CallFrame {
+            function: String::from("<module>"),
+            file: Some(resolved.display().to_string()),
+            call_line: import.keyword.get_line(),
+        });
+
+        let environment = Rc::new(RefCell::new(Environment::new_enclosing(
+            self.globals.clone(),
+        )));
+        let statements = self.prepare(statements);
+        let result = self
+            .run(&environment, &statements)
+            .map_err(|err| self.unwind(err));
+
+        self.calls.pop();
+        let exported = std::mem::replace(&mut self.exports, previous_exports);
+        self.current_file = previous_file;
+
+        if let Err(err) = result {
+            self.modules.finish(None);
+            return Err(err);
+        }
+
+        let mut exports = ValueMap::new();
+        for name in exported {
+            let value = environment.borrow().get(&name)?;
+            exports.insert(HashKey::String(name.get_lexeme().to_string()), value);
+        }
+
+        // Scripts can store the module in itself, so it is tracked like any
+        // other map.
+        let exports = Rc::new(RefCell::new(exports));
+        heap::track_map(&exports);
+        self.modules.finish(Some(exports.clone()));
+
+        Ok(exports)
     }
 
     // fn execute(&self, environment: &mut environment::Environment, statement: parser::Statement) {
@@ -190,149 +1016,361 @@ impl Interpreter {
     //     }
     // }
 
-    fn evaluate(&self, environment: &mut Environment, expr: Expr) -> Value {
-        match expr {
+    fn evaluate(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        expr: &Expr,
+    ) -> Result<Value, RuntimeError> {
+        let value = match expr {
             Expr::Literal(literal) => match literal {
-                Literal::String(string) => Value::String(string),
-                Literal::Integer(integer) => Value::Integer(integer),
-                Literal::Float(float) => Value::Float(float),
-                Literal::Boolean(boolean) => Value::Boolean(boolean),
+                Literal::String(string) => Value::String(string.clone()),
+                Literal::Integer(integer) => Value::Integer(*integer),
+                Literal::Float(float) => Value::Float(*float),
+                Literal::Boolean(boolean) => Value::Boolean(*boolean),
                 Literal::Nil => Value::Nil,
             },
-            Expr::GroupingExpr(expr) => self.evaluate(environment, expr.expr.into()),
+            Expr::GroupingExpr(expr) => self.evaluate(environment, &expr.expr)?,
             Expr::UnaryExpr(unary) => {
-                let unary_expr = *unary;
-                let operator = unary_expr.get_operator().clone();
-                let expr = unary_expr.get_right().clone();
-
-                let right = self.evaluate(environment, expr);
-                match operator.token_type {
-                    TokenType::Minus => match right {
-                        Value::Integer(integer) => Value::Integer(-integer),
-                        Value::Float(float) => Value::Float(-float),
-                        _ => panic!("Invalid operand for unary minus"),
-                    },
-                    TokenType::Bang => Value::Boolean(!right.is_truthy()),
-                    _ => panic!("Invalid unary operator"),
-                }
+                let right = self.evaluate(environment, unary.get_right())?;
+                operators::unary(unary.get_operator(), right)?
             }
             Expr::BinaryExpr(binary) => {
-                let left = binary.get_left();
-                let operator = binary.get_operator().clone();
-                let right = binary.get_right();
-
-                let left = self.evaluate(environment, left);
-                let right = self.evaluate(environment, right);
-                match operator.token_type {
-                    TokenType::Plus => match (left.clone(), right.clone()) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            Value::Integer(left + right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => Value::Float(left + right),
-                        (Value::String(left), Value::String(right)) => Value::String(left + &right),
-                        _ => panic!(
-                            "Invalid operands for addition: {:?} - {:?}",
-                            left.clone(),
-                            right.clone()
-                        ),
-                    },
-                    TokenType::Minus => match (left, right) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            Value::Integer(left - right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => Value::Float(left - right),
-                        _ => panic!("Invalid operands for subtraction"),
-                    },
-                    TokenType::Star => match (left, right) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            Value::Integer(left * right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => Value::Float(left * right),
-                        _ => panic!("Invalid operands for multiplication"),
-                    },
-                    TokenType::Slash => match (left, right) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            if right.eq(&i64::from(0)) {
-                                panic!("Division by zero");
-                            }
-
-                            Value::Integer(left / right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => {
-                            if right.eq(&f64::from(0)) {
-                                panic!("Division by zero");
-                            }
-
-                            Value::Float(left / right)
-                        }
-                        _ => panic!("Invalid operands for division"),
-                    },
-                    TokenType::Greater => match (left, right) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            Value::Boolean(left > right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => Value::Boolean(left > right),
-                        _ => panic!("Invalid operands for greater than"),
-                    },
-                    TokenType::GreaterEqual => match (left, right) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            Value::Boolean(left >= right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => Value::Boolean(left >= right),
-                        _ => panic!("Invalid operands for greater than or equal"),
-                    },
-                    TokenType::Less => match (left, right) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            Value::Boolean(left < right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => Value::Boolean(left < right),
-                        _ => panic!("Invalid operands for less than"),
-                    },
-                    TokenType::LessEqual => match (left, right) {
-                        (Value::Integer(left), Value::Integer(right)) => {
-                            Value::Boolean(left <= right)
-                        }
-                        (Value::Float(left), Value::Float(right)) => Value::Boolean(left <= right),
-                        _ => panic!("Invalid operands for less than or equal"),
-                    },
-                    TokenType::EqualEqual => match (left, right) {
-                        (Value::Boolean(right), Value::Boolean(left)) => {
-                            Value::Boolean(left == right)
-                        }
-                        (Value::Integer(right), Value::Integer(left)) => {
-                            Value::Boolean(left.eq(&right))
-                        }
-                        (Value::Float(right), Value::Float(left)) => {
-                            Value::Boolean(left.eq(&right))
-                        }
-                        (Value::String(right), Value::String(left)) => {
-                            Value::Boolean(left.eq(&right))
-                        }
-                        _ => panic!("Invalid operands for equality"),
-                    },
-                    TokenType::BangEqual => match (left, right) {
-                        (Value::Boolean(right), Value::Boolean(left)) => {
-                            Value::Boolean(left != right)
-                        }
-                        (Value::Integer(right), Value::Integer(left)) => {
-                            Value::Boolean(!left.eq(&right))
-                        }
-                        (Value::Float(right), Value::Float(left)) => {
-                            Value::Boolean(!left.eq(&right))
-                        }
-                        (Value::String(right), Value::String(left)) => {
-                            Value::Boolean(!left.eq(&right))
-                        }
-                        _ => panic!("Invalid operands for inequality"),
-                    },
-                    _ => panic!("Invalid binary operator"),
+                let left = self.evaluate(environment, binary.get_left())?;
+                let right = self.evaluate(environment, binary.get_right())?;
+                let value = operators::binary(binary.get_operator(), left, right)?;
+                self.allocate(&value, Some(binary.get_operator().get_line()))?;
+                value
+            }
+            Expr::LogicalExpr(logical) => {
+                let left = self.evaluate(environment, logical.get_left())?;
+
+                // `or` stops at the first truthy operand, `and` at the first falsy one.
+                let decided = match logical.get_operator().token_type {
+                    TokenType::Or => left.is_truthy(),
+                    _ => !left.is_truthy(),
+                };
+
+                if decided {
+                    left
+                } else {
+                    self.evaluate(environment, logical.get_right())?
                 }
             }
-            Expr::VarDeclaration(variable) => {
-                let name = variable.get_lexeme().clone();
-                let value = environment.get(name);
+            Expr::VarDeclaration(variable) => environment.borrow().get(variable)?,
+            Expr::AssignExpr(assign) => {
+                let value = self.evaluate(environment, assign.get_value())?;
+                environment
+                    .borrow_mut()
+                    .assign(assign.get_name(), value.clone())?;
                 value
             }
+            Expr::MapExpr(map) => {
+                let mut values = ValueMap::new();
+
+                for (key, value) in map.get_entries() {
+                    let key = self.evaluate(environment, key)?;
+                    let key = self.hash_key(map.get_brace(), &key)?;
+                    let value = self.evaluate(environment, value)?;
+                    values.insert(key, value);
+                }
+
+                let value = Value::map(values);
+                self.allocate(&value, Some(map.get_brace().get_line()))?;
+                value
+            }
+            Expr::IndexExpr(index) => {
+                let object = self.evaluate(environment, index.get_object())?;
+                let key = self.evaluate(environment, index.get_index())?;
+                self.index(index.get_bracket(), object, key)?
+            }
+            Expr::SetIndexExpr(set) => {
+                let object = self.evaluate(environment, set.get_object())?;
+                let key = self.evaluate(environment, set.get_index())?;
+                let value = self.evaluate(environment, set.get_value())?;
+                self.allocate_entry(&object, set.get_bracket().get_line())?;
+                self.set_index(set.get_bracket(), object, key, value)?
+            }
+            Expr::CallExpr(call) => {
+                let callee = self.evaluate(environment, call.get_callee())?;
+
+                let mut arguments = Vec::<Value>::new();
+                for argument in call.get_arguments() {
+                    arguments.push(self.evaluate(environment, argument)?);
+                }
+
+                self.call(call.get_paren(), callee, arguments)?
+            }
+            Expr::GetExpr(get) => {
+                let object = self.evaluate(environment, get.get_object())?;
+                self.get_property(get.get_name(), object)?
+            }
+            Expr::ListExpr(list) => {
+                let mut elements = Vec::<Value>::new();
+                for element in list.get_elements() {
+                    elements.push(self.evaluate(environment, element)?);
+                }
+
+                let value = Value::list(elements);
+                self.allocate(&value, Some(list.get_bracket().get_line()))?;
+                value
+            }
+            Expr::SliceExpr(slice) => {
+                let object = self.evaluate(environment, slice.get_object())?;
+                let start = match slice.get_start() {
+                    Some(start) => Some(self.evaluate(environment, start)?),
+                    None => None,
+                };
+                let end = match slice.get_end() {
+                    Some(end) => Some(self.evaluate(environment, end)?),
+                    None => None,
+                };
+
+                let value = self.slice(slice.get_bracket(), object, start, end)?;
+                self.allocate(&value, Some(slice.get_bracket().get_line()))?;
+                value
+            }
+        };
+
+        Ok(value)
+    }
+
+    pub(crate) fn call(&mut self, paren: &Token, callee: Value, arguments: Vec<Value>) -> Result<Value, RuntimeError> {
+        match callee {
+            Value::Function(function) => {
+                if arguments.len() != function.arity() {
+                    return Err(RuntimeError::new(
+                        paren.clone(),
+                        &format!(
+                            "{}() expected {} arguments but got {}.",
+                            function.get_name(),
+                            function.arity(),
+                            arguments.len()
+                        ),
+                    ));
+                }
+
+                if self.calls.len() >= self.max_depth {
+                    return Err(RuntimeError::new(paren.clone(), "Stack overflow.").with_kind(ErrorKind::StackOverflow));
+                }
+
+                let mut scope = Environment::new_enclosing(function.get_closure().clone());
+                for (param, argument) in function.get_declaration().params.iter().zip(arguments) {
+                    scope.define(param.get_symbol(), argument);
+                }
+
+                let previous_file = match function.get_file() {
+                    Some(file) => self.current_file.replace(PathBuf::from(file)),
+                    None => self.current_file.take(),
+                };
+                self.calls.push(CallFrame {
+                    function: function.get_name().to_string(),
+                    file: function.get_file().map(String::from),
+                    call_line: paren.get_line(),
+                });
+
+                let scope = Rc::new(RefCell::new(scope));
+                // Deep recursion in taco is deep recursion here too, so make
+                // sure there is room for the body before running it.
+                let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT, || match function.get_chunk() {
+                    Some(chunk) => self.run_chunk(&scope, chunk),
+                    None => self.execute_statements(&scope, &function.get_declaration().body),
+                });
+                let result = result.map_err(|err| self.unwind(err));
+
+                self.calls.pop();
+                self.current_file = previous_file;
+                self.maybe_collect();
+
+                match result? {
+                    Flow::Return(value) => Ok(value),
+                    Flow::Next => Ok(Value::Nil),
+                }
+            }
+            Value::NativeFunction(native) => {
+                if !native.get_arity().accepts(arguments.len()) {
+                    return Err(RuntimeError::new(
+                        paren.clone(),
+                        &format!(
+                            "{}() expected {} arguments but got {}.",
+                            native.get_name(),
+                            native.get_arity().describe(),
+                            arguments.len()
+                        ),
+                    ));
+                }
+
+                let value = match native.call(self, arguments) {
+                    Ok(value) => value,
+                    Err(message) => {
+                        let error = RuntimeError::new(paren.clone(), &message);
+                        return Err(match self.meter.get_exceeded() {
+                            Some(kind) => error.with_kind(kind),
+                            None => error,
+                        });
+                    }
+                };
+
+                self.allocate(&value, Some(paren.get_line()))?;
+                Ok(value)
+            }
+            _ => Err(RuntimeError::new(
+                paren.clone(),
+                &format!("Cannot call a value of type {}.", callee.type_name()),
+            )),
+        }
+    }
+
+    pub(crate) fn hash_key(&self, token: &Token, key: &Value) -> Result<HashKey, RuntimeError> {
+        match HashKey::from_value(key) {
+            Some(key) => Ok(key),
+            None => Err(RuntimeError::new(
+                token.clone(),
+                &format!("Unhashable key type '{}'.", key.type_name()),
+            )),
         }
     }
+
+    pub(crate) fn get_property(&self, name: &Token, object: Value) -> Result<Value, RuntimeError> {
+        let property = match &object {
+            Value::String(string) => {
+                stdlib::string::method(string, name.get_lexeme()).map(Value::NativeFunction)
+            }
+            // `map.name` is shorthand for `map["name"]`, which is also how
+            // module members such as `math.sqrt` are read.
+            Value::Map(map) => map
+                .borrow()
+                .get(&HashKey::String(name.get_lexeme().to_string()))
+                .cloned(),
+            Value::Error(error) => match name.get_lexeme().as_str() {
+                "message" => Some(Value::String(error.get_message().to_string())),
+                "line" => Some(Value::Integer(error.get_line() as i64)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        match property {
+            Some(property) => Ok(property),
+            None => Err(RuntimeError::new(
+                name.clone(),
+                &format!(
+                    "Undefined property '{}' on {}.",
+                    name.get_lexeme(),
+                    object.type_name()
+                ),
+            )),
+        }
+    }
+
+    fn list_position(&self, bracket: &Token, index: &Value, len: usize) -> Result<usize, RuntimeError> {
+        let index = match index {
+            Value::Integer(index) => *index,
+            other => {
+                return Err(RuntimeError::new(
+                    bracket.clone(),
+                    &format!("List indices must be integers, not {}.", other.type_name()),
+                ))
+            }
+        };
+
+        match normalize_index(index, len) {
+            Some(position) => Ok(position),
+            None => Err(RuntimeError::new(bracket.clone(), "List index out of range.")),
+        }
+    }
+
+    pub(crate) fn slice(
+        &self,
+        bracket: &Token,
+        object: Value,
+        start: Option<Value>,
+        end: Option<Value>,
+    ) -> Result<Value, RuntimeError> {
+        let bound = |value: Option<Value>| match value {
+            None | Some(Value::Nil) => Ok(None),
+            Some(Value::Integer(integer)) => Ok(Some(integer)),
+            Some(other) => Err(RuntimeError::new(
+                bracket.clone(),
+                &format!("Slice bounds must be integers, not {}.", other.type_name()),
+            )),
+        };
+        let start = bound(start)?;
+        let end = bound(end)?;
+
+        match object {
+            Value::String(string) => Ok(Value::String(stdlib::string::slice(&string, start, end))),
+            Value::List(list) => {
+                let list = list.borrow();
+                let (start, end) = slice_bounds(list.len(), start, end);
+                Ok(Value::list(list[start..end].to_vec()))
+            }
+            _ => Err(RuntimeError::new(
+                bracket.clone(),
+                &format!("Cannot slice {}.", object.type_name()),
+            )),
+        }
+    }
+
+    pub(crate) fn index(&self, bracket: &Token, object: Value, key: Value) -> Result<Value, RuntimeError> {
+        match object {
+            Value::String(string) => {
+                let index = match key {
+                    Value::Integer(index) => index,
+                    other => {
+                        return Err(RuntimeError::new(
+                            bracket.clone(),
+                            &format!("String indices must be integers, not {}.", other.type_name()),
+                        ))
+                    }
+                };
+
+                match stdlib::string::char_at(&string, index) {
+                    Some(character) => Ok(Value::String(character)),
+                    None => Err(RuntimeError::new(bracket.clone(), "String index out of range.")),
+                }
+            }
+            Value::List(list) => {
+                let list = list.borrow();
+                let position = self.list_position(bracket, &key, list.len())?;
+                Ok(list[position].clone())
+            }
+            // A missing key reads as nil, so `m[key] == nil` tests for one.
+            Value::Map(map) => {
+                let hash_key = self.hash_key(bracket, &key)?;
+                Ok(map.borrow().get(&hash_key).cloned().unwrap_or(Value::Nil))
+            }
+            _ => Err(RuntimeError::new(
+                bracket.clone(),
+                &format!("Cannot index into {}.", object.type_name()),
+            )),
+        }
+    }
+
+    pub(crate) fn set_index(
+        &self,
+        bracket: &Token,
+        object: Value,
+        key: Value,
+        value: Value,
+    ) -> Result<Value, RuntimeError> {
+        match object {
+            Value::List(list) => {
+                let mut list = list.borrow_mut();
+                let position = self.list_position(bracket, &key, list.len())?;
+                list[position] = value.clone();
+            }
+            Value::Map(map) => {
+                let key = self.hash_key(bracket, &key)?;
+                map.borrow_mut().insert(key, value.clone());
+            }
+            _ => {
+                return Err(RuntimeError::new(
+                    bracket.clone(),
+                    &format!("Cannot assign to an index of {}.", object.type_name()),
+                ))
+            }
+        }
+
+        Ok(value)
+    }
 }
@@ -0,0 +1,166 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    error::interpreter::ErrorKind,
+    interpreter::{interpreter::Value, map::HashKey},
+};
+
+// The clock is only read every this many steps; reading it is far slower
+// than running a step.
+const CLOCK_INTERVAL: u64 = 256;
+
+/// Caps on what a script may do, for running code that is not trusted.
+/// Each limit is unlimited when `None`, and applies to each script or call
+/// the host runs separately. Calls that host functions make back into the
+/// interpreter count towards the run they were made from.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Limits {
+    /// Statements executed by the tree-walker, or instructions by the VM.
+    pub max_steps: Option<u64>,
+    /// Approximate bytes allocated for strings, lists and maps over the
+    /// whole run. Freeing a value does not give its bytes back, so this
+    /// bounds how much a script allocates, not how much it holds at once.
+    pub max_allocation: Option<usize>,
+    /// Bytes the script prints or writes to stderr.
+    pub max_output: Option<usize>,
+    /// Wall-clock time the script may run for.
+    pub timeout: Option<Duration>,
+}
+
+/// What the running script has used so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    pub steps: u64,
+    /// Bytes allocated so far, including any since freed.
+    pub allocated: usize,
+    pub output: usize,
+}
+
+/// Counts what a script uses and reports the first limit it exceeds.
+#[derive(Debug, Clone, Default)]
+pub struct Meter {
+    limits: Limits,
+    usage: Usage,
+    deadline: Option<Instant>,
+    // Once a limit is exceeded, everything that is metered fails with it.
+    exceeded: Option<ErrorKind>,
+}
+
+impl Meter {
+    pub fn get_limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    pub fn get_usage(&self) -> &Usage {
+        &self.usage
+    }
+
+    pub fn get_exceeded(&self) -> Option<ErrorKind> {
+        self.exceeded
+    }
+
+    /// Starts metering a new script.
+    pub fn start(&mut self) {
+        self.usage = Usage::default();
+        self.deadline = self.limits.timeout.map(|timeout| Instant::now() + timeout);
+        self.exceeded = None;
+    }
+
+    fn exceed(&mut self, kind: ErrorKind) -> Result<(), ErrorKind> {
+        self.exceeded = Some(kind);
+        Err(kind)
+    }
+
+    pub fn step(&mut self) -> Result<(), ErrorKind> {
+        if let Some(kind) = self.exceeded {
+            return Err(kind);
+        }
+
+        self.usage.steps += 1;
+
+        if let Some(max_steps) = self.limits.max_steps {
+            if self.usage.steps > max_steps {
+                return self.exceed(ErrorKind::StepLimit);
+            }
+        }
+
+        if let Some(deadline) = self.deadline {
+            if self.usage.steps.is_multiple_of(CLOCK_INTERVAL) && Instant::now() >= deadline {
+                return self.exceed(ErrorKind::Timeout);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Charges for a newly created value, counting only the value itself:
+    /// the elements of a list were charged for when they were created.
+    pub fn allocate(&mut self, value: &Value) -> Result<(), ErrorKind> {
+        let bytes = match value {
+            Value::String(string) => string.len(),
+            Value::List(list) => list.borrow().len() * std::mem::size_of::<Value>(),
+            Value::Map(map) => map.borrow().len() * Meter::map_entry(),
+            _ => return Ok(()),
+        };
+
+        self.allocate_bytes(bytes)
+    }
+
+    /// The approximate cost of adding one entry to a map.
+    pub fn map_entry() -> usize {
+        std::mem::size_of::<Value>() + std::mem::size_of::<HashKey>()
+    }
+
+    pub fn allocate_bytes(&mut self, bytes: usize) -> Result<(), ErrorKind> {
+        self.usage.allocated = self.usage.allocated.saturating_add(bytes);
+
+        match self.limits.max_allocation {
+            Some(max_allocation) if self.usage.allocated > max_allocation => {
+                self.exceed(ErrorKind::AllocationLimit)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn output(&mut self, bytes: usize) -> Result<(), ErrorKind> {
+        if let Some(kind) = self.exceeded {
+            return Err(kind);
+        }
+
+        self.usage.output = self.usage.output.saturating_add(bytes);
+
+        match self.limits.max_output {
+            Some(max_output) if self.usage.output > max_output => {
+                self.exceed(ErrorKind::OutputLimit)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The message for an error raised because `kind` was exceeded.
+    pub fn describe(&self, kind: ErrorKind) -> String {
+        match kind {
+            ErrorKind::StepLimit => format!(
+                "Step limit of {} exceeded.",
+                self.limits.max_steps.unwrap_or_default()
+            ),
+            ErrorKind::AllocationLimit => format!(
+                "Allocation limit of {} bytes exceeded.",
+                self.limits.max_allocation.unwrap_or_default()
+            ),
+            ErrorKind::OutputLimit => format!(
+                "Output limit of {} bytes exceeded.",
+                self.limits.max_output.unwrap_or_default()
+            ),
+            ErrorKind::Timeout => format!(
+                "Time limit of {} ms exceeded.",
+                self.limits.timeout.unwrap_or_default().as_millis()
+            ),
+            ErrorKind::Runtime | ErrorKind::StackOverflow => String::from("Limit exceeded."),
+        }
+    }
+}
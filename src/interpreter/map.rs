@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use crate::interpreter::interpreter::Value;
+
+/// The subset of values that can be used as map keys.
+///
+/// Floats are rejected because `NaN != NaN` and rounding makes lookups
+/// unreliable; lists and maps are rejected because they are mutable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    Integer(i64),
+    String(String),
+    Boolean(bool),
+    Nil,
+}
+
+impl HashKey {
+    /// Returns `None` when the value is not hashable.
+    pub fn from_value(value: &Value) -> Option<HashKey> {
+        match value {
+            Value::Integer(integer) => Some(HashKey::Integer(*integer)),
+            Value::String(string) => Some(HashKey::String(string.clone())),
+            Value::Boolean(boolean) => Some(HashKey::Boolean(*boolean)),
+            Value::Nil => Some(HashKey::Nil),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            HashKey::Integer(integer) => Value::Integer(*integer),
+            HashKey::String(string) => Value::String(string.clone()),
+            HashKey::Boolean(boolean) => Value::Boolean(*boolean),
+            HashKey::Nil => Value::Nil,
+        }
+    }
+}
+
+/// An insertion-ordered map of taco values.
+///
+/// Iteration always yields entries in the order their keys were first
+/// inserted; overwriting an existing key keeps its original position.
+#[derive(Debug, Clone, Default)]
+pub struct ValueMap {
+    indices: HashMap<HashKey, usize>,
+    entries: Vec<(HashKey, Value)>,
+}
+
+impl ValueMap {
+    pub fn new() -> ValueMap {
+        ValueMap {
+            indices: HashMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &HashKey) -> Option<&Value> {
+        self.indices.get(key).map(|index| &self.entries[*index].1)
+    }
+
+    pub fn insert(&mut self, key: HashKey, value: Value) {
+        match self.indices.get(&key) {
+            Some(index) => self.entries[*index].1 = value,
+            None => {
+                self.indices.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&HashKey, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+}
+
+impl PartialEq for ValueMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
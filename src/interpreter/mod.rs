@@ -1 +1,19 @@
-pub mod interpreter;
\ No newline at end of file
+#[allow(clippy::module_inception)]
+pub mod interpreter;
+#[doc(hidden)]
+pub mod map;
+#[doc(hidden)]
+pub mod native;
+pub(crate) mod module;
+pub(crate) mod error_value;
+pub(crate) mod function;
+pub(crate) mod frame;
+pub(crate) mod operators;
+#[doc(hidden)]
+pub mod heap;
+#[doc(hidden)]
+pub mod limits;
+pub mod convert;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod output;
\ No newline at end of file
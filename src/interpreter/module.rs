@@ -0,0 +1,66 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::interpreter::map::ValueMap;
+
+/// Tracks which modules have been loaded and which are still executing, so
+/// every module runs at most once and import cycles can be reported.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleLoader {
+    cache: HashMap<PathBuf, Rc<RefCell<ValueMap>>>,
+    loading: Vec<PathBuf>,
+}
+
+impl ModuleLoader {
+    pub fn new() -> ModuleLoader {
+        ModuleLoader {
+            cache: HashMap::new(),
+            loading: Vec::new(),
+        }
+    }
+
+    /// Resolves an import path relative to the directory of the importing
+    /// file, or to the working directory when there is none (e.g. the REPL).
+    pub fn resolve(importer: Option<&Path>, path: &str) -> PathBuf {
+        match importer.and_then(Path::parent) {
+            Some(directory) => directory.join(path),
+            None => PathBuf::from(path),
+        }
+    }
+
+    pub fn get(&self, path: &Path) -> Option<Rc<RefCell<ValueMap>>> {
+        self.cache.get(path).cloned()
+    }
+
+    /// Describes the cycle that importing `path` would close, or returns
+    /// `None` when `path` is not currently being loaded.
+    pub fn cycle(&self, path: &Path) -> Option<String> {
+        let start = self.loading.iter().position(|loading| loading == path)?;
+
+        let mut chain = self.loading[start..]
+            .iter()
+            .map(|loading| loading.display().to_string())
+            .collect::<Vec<String>>();
+        chain.push(path.display().to_string());
+
+        Some(chain.join(" -> "))
+    }
+
+    pub fn begin(&mut self, path: PathBuf) {
+        self.loading.push(path);
+    }
+
+    /// Marks the innermost module as done, caching its exports if it
+    /// finished successfully.
+    pub fn finish(&mut self, exports: Option<Rc<RefCell<ValueMap>>>) {
+        if let Some(path) = self.loading.pop() {
+            if let Some(exports) = exports {
+                self.cache.insert(path, exports);
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    fmt::{Debug, Display, Formatter},
+    rc::Rc,
+};
+
+use crate::interpreter::{
+    convert::{FromValue, IntoValue},
+    interpreter::{Interpreter, Value},
+};
+
+/// Host code behind a native function. Errors are plain messages; the
+/// interpreter attaches the call site when turning them into runtime errors.
+pub type NativeFn = dyn Fn(&mut Interpreter, Vec<Value>) -> Result<Value, String>;
+
+/// How many arguments a native function accepts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arity {
+    Exact(usize),
+    Range(usize, usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn accepts(&self, count: usize) -> bool {
+        match self {
+            Arity::Exact(arity) => count == *arity,
+            Arity::Range(min, max) => count >= *min && count <= *max,
+            Arity::AtLeast(min) => count >= *min,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Arity::Exact(arity) => arity.to_string(),
+            Arity::Range(min, max) => format!("{} to {}", min, max),
+            Arity::AtLeast(min) => format!("at least {}", min),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NativeFunction {
+    name: String,
+    arity: Arity,
+    function: Rc<NativeFn>,
+}
+
+impl NativeFunction {
+    pub fn new<F>(name: &str, arity: Arity, function: F) -> NativeFunction
+    where
+        F: Fn(&mut Interpreter, Vec<Value>) -> Result<Value, String> + 'static,
+    {
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            function: Rc::new(function),
+        }
+    }
+
+    /// Wraps a Rust closure with typed arguments, converting each argument
+    /// from a taco value and the result back into one.
+    pub fn from_host<F, Args>(name: &str, function: F) -> NativeFunction
+    where
+        F: HostFunction<Args> + 'static,
+    {
+        let owned = name.to_string();
+        NativeFunction::new(name, Arity::Exact(F::arity()), move |_, arguments| {
+            function.call(&owned, arguments)
+        })
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_arity(&self) -> Arity {
+        self.arity
+    }
+
+    pub fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, String> {
+        (self.function)(interpreter, arguments)
+    }
+}
+
+impl Debug for NativeFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<native fn {}>", self.name)
+    }
+}
+
+impl PartialEq for NativeFunction {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.function, &other.function)
+    }
+}
+
+/// What a typed host function may return: a value, or a `Result` whose
+/// error becomes a runtime error in the script.
+pub trait IntoNativeResult {
+    fn into_native_result(self) -> Result<Value, String>;
+}
+
+impl<T: IntoValue> IntoNativeResult for T {
+    fn into_native_result(self) -> Result<Value, String> {
+        Ok(self.into_value())
+    }
+}
+
+impl<T: IntoValue, E: Display> IntoNativeResult for Result<T, E> {
+    fn into_native_result(self) -> Result<Value, String> {
+        self.map(IntoValue::into_value).map_err(|error| error.to_string())
+    }
+}
+
+/// A Rust closure that scripts can call, taking arguments of the types in
+/// the tuple `Args`. The call's arity is checked before it runs.
+pub trait HostFunction<Args> {
+    fn arity() -> usize;
+
+    fn call(&self, name: &str, arguments: Vec<Value>) -> Result<Value, String>;
+}
+
+fn argument<T: FromValue>(name: &str, index: usize, value: Value) -> Result<T, String> {
+    T::from_value(value).map_err(|error| {
+        format!(
+            "{}() expects {} as argument {} but got {}.",
+            name,
+            error.expected,
+            index + 1,
+            error.found
+        )
+    })
+}
+
+macro_rules! host_function {
+    ($($arg:ident),*) => {
+        impl<Func, R, $($arg),*> HostFunction<($($arg,)*)> for Func
+        where
+            Func: Fn($($arg),*) -> R,
+            R: IntoNativeResult,
+            $($arg: FromValue,)*
+        {
+            fn arity() -> usize {
+                <[&str]>::len(&[$(stringify!($arg)),*])
+            }
+
+            #[allow(non_snake_case, unused_variables, unused_mut)]
+            fn call(&self, name: &str, arguments: Vec<Value>) -> Result<Value, String> {
+                let mut arguments = arguments.into_iter().enumerate();
+                $(
+                    let $arg = match arguments.next() {
+                        Some((index, value)) => argument::<$arg>(name, index, value)?,
+                        None => return Err(format!("{}() is missing an argument.", name)),
+                    };
+                )*
+
+                self($($arg),*).into_native_result()
+            }
+        }
+    };
+}
+
+host_function!();
+host_function!(A);
+host_function!(A, B);
+host_function!(A, B, C);
+host_function!(A, B, C, D);
+host_function!(A, B, C, D, E);
+host_function!(A, B, C, D, E, F);
@@ -0,0 +1,191 @@
+use std::rc::Rc;
+
+use crate::{
+    error::interpreter::RuntimeError,
+    interpreter::interpreter::Value,
+    token::tokens::{Token, TokenType},
+};
+
+/// Applies a unary operator. Shared by both backends and the optimizer so
+/// they agree on results and on which operations fail.
+pub fn unary(operator: &Token, right: Value) -> Result<Value, RuntimeError> {
+    match operator.token_type {
+        TokenType::Minus => match right {
+            Value::Integer(integer) => checked(operator, integer.checked_neg()),
+            Value::Float(float) => Ok(Value::Float(-float)),
+            _ => Err(RuntimeError::new(operator.clone(), "Invalid operand for unary minus")),
+        },
+        TokenType::Bang => Ok(Value::Boolean(!right.is_truthy())),
+        _ => Err(RuntimeError::new(operator.clone(), "Invalid unary operator")),
+    }
+}
+
+fn checked(operator: &Token, result: Option<i64>) -> Result<Value, RuntimeError> {
+    match result {
+        Some(result) => Ok(Value::Integer(result)),
+        None => Err(RuntimeError::new(operator.clone(), "Integer overflow")),
+    }
+}
+
+/// Applies a binary operator. Like `unary`, this is the single definition of
+/// what each operator does.
+pub fn binary(operator: &Token, left: Value, right: Value) -> Result<Value, RuntimeError> {
+    let value = match operator.token_type {
+        TokenType::Plus => match (left.clone(), right.clone()) {
+            (Value::Integer(left), Value::Integer(right)) => {
+                checked(operator, left.checked_add(right))?
+            }
+            (Value::Float(left), Value::Float(right)) => Value::Float(left + right),
+            (Value::String(left), Value::String(right)) => Value::String(left + &right),
+            _ => {
+                return Err(RuntimeError::new(
+                    operator.clone(),
+                    &format!(
+                        "Invalid operands for addition: {} - {}",
+                        left.type_name(),
+                        right.type_name()
+                    ),
+                ))
+            }
+        },
+        TokenType::Minus => match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => {
+                checked(operator, left.checked_sub(right))?
+            }
+            (Value::Float(left), Value::Float(right)) => Value::Float(left - right),
+            _ => return Err(RuntimeError::new(operator.clone(), "Invalid operands for subtraction")),
+        },
+        TokenType::Star => match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => {
+                checked(operator, left.checked_mul(right))?
+            }
+            (Value::Float(left), Value::Float(right)) => Value::Float(left * right),
+            _ => {
+                return Err(RuntimeError::new(
+                    operator.clone(),
+                    "Invalid operands for multiplication",
+                ))
+            }
+        },
+        TokenType::Slash => match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => {
+                if right == 0 {
+                    return Err(RuntimeError::new(operator.clone(), "Division by zero"));
+                }
+
+                checked(operator, left.checked_div(right))?
+            }
+            (Value::Float(left), Value::Float(right)) => {
+                if right == 0.0 {
+                    return Err(RuntimeError::new(operator.clone(), "Division by zero"));
+                }
+
+                Value::Float(left / right)
+            }
+            _ => return Err(RuntimeError::new(operator.clone(), "Invalid operands for division")),
+        },
+        TokenType::Greater => match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => Value::Boolean(left > right),
+            (Value::Float(left), Value::Float(right)) => Value::Boolean(left > right),
+            _ => return Err(RuntimeError::new(operator.clone(), "Invalid operands for greater than")),
+        },
+        TokenType::GreaterEqual => match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => Value::Boolean(left >= right),
+            (Value::Float(left), Value::Float(right)) => Value::Boolean(left >= right),
+            _ => {
+                return Err(RuntimeError::new(
+                    operator.clone(),
+                    "Invalid operands for greater than or equal",
+                ))
+            }
+        },
+        TokenType::Less => match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => Value::Boolean(left < right),
+            (Value::Float(left), Value::Float(right)) => Value::Boolean(left < right),
+            _ => return Err(RuntimeError::new(operator.clone(), "Invalid operands for less than")),
+        },
+        TokenType::LessEqual => match (left, right) {
+            (Value::Integer(left), Value::Integer(right)) => Value::Boolean(left <= right),
+            (Value::Float(left), Value::Float(right)) => Value::Boolean(left <= right),
+            _ => {
+                return Err(RuntimeError::new(
+                    operator.clone(),
+                    "Invalid operands for less than or equal",
+                ))
+            }
+        },
+        // Any two values can be compared; values of different types are
+        // never equal.
+        TokenType::EqualEqual => Value::Boolean(equal(&left, &right)),
+        TokenType::BangEqual => Value::Boolean(!equal(&left, &right)),
+        _ => return Err(RuntimeError::new(operator.clone(), "Invalid binary operator")),
+    };
+
+    Ok(value)
+}
+
+/// Whether two values are equal, comparing lists and maps by contents. A
+/// pair of containers already being compared further up counts as equal,
+/// so lists and maps that contain themselves compare without recursing
+/// forever.
+pub fn equal(left: &Value, right: &Value) -> bool {
+    equal_tracked(left, right, &mut Vec::new())
+}
+
+// `open` holds the pairs of lists and maps being compared further up, the
+// same way `Interpreter::format` tracks the containers it is inside.
+fn equal_tracked(left: &Value, right: &Value, open: &mut Vec<(*const (), *const ())>) -> bool {
+    match (left, right) {
+        (Value::Integer(left), Value::Integer(right)) => left == right,
+        (Value::Float(left), Value::Float(right)) => left == right,
+        (Value::String(left), Value::String(right)) => left == right,
+        (Value::Boolean(left), Value::Boolean(right)) => left == right,
+        (Value::List(left), Value::List(right)) => {
+            if Rc::ptr_eq(left, right) {
+                return true;
+            }
+
+            let pair = (Rc::as_ptr(left) as *const (), Rc::as_ptr(right) as *const ());
+            if open.contains(&pair) {
+                return true;
+            }
+
+            open.push(pair);
+            let (left, right) = (left.borrow(), right.borrow());
+            let equal = left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right.iter())
+                    .all(|(left, right)| equal_tracked(left, right, open));
+            open.pop();
+
+            equal
+        }
+        (Value::Map(left), Value::Map(right)) => {
+            if Rc::ptr_eq(left, right) {
+                return true;
+            }
+
+            let pair = (Rc::as_ptr(left) as *const (), Rc::as_ptr(right) as *const ());
+            if open.contains(&pair) {
+                return true;
+            }
+
+            open.push(pair);
+            let (left, right) = (left.borrow(), right.borrow());
+            let equal = left.len() == right.len()
+                && left.iter().all(|(key, value)| match right.get(key) {
+                    Some(other) => equal_tracked(value, other, open),
+                    None => false,
+                });
+            open.pop();
+
+            equal
+        }
+        (Value::Function(left), Value::Function(right)) => Rc::ptr_eq(left, right),
+        (Value::NativeFunction(left), Value::NativeFunction(right)) => left == right,
+        (Value::Error(left), Value::Error(right)) => left == right,
+        (Value::Nil, Value::Nil) => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    io::{self, Write},
+    rc::Rc,
+};
+
+/// Where a script's output goes: `print` writes to `stdout`, and
+/// `io.eprint` to `stderr`. Both are the process's streams by default.
+#[derive(Clone)]
+pub struct Output {
+    stdout: Rc<RefCell<dyn Write>>,
+    stderr: Rc<RefCell<dyn Write>>,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Output::new(io::stdout(), io::stderr())
+    }
+}
+
+impl Debug for Output {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Output")
+    }
+}
+
+impl Output {
+    pub fn new(stdout: impl Write + 'static, stderr: impl Write + 'static) -> Output {
+        Output {
+            stdout: Rc::new(RefCell::new(stdout)),
+            stderr: Rc::new(RefCell::new(stderr)),
+        }
+    }
+
+    pub fn write_stdout(&self, text: &str) -> io::Result<()> {
+        self.stdout.borrow_mut().write_all(text.as_bytes())
+    }
+
+    pub fn write_stderr(&self, text: &str) -> io::Result<()> {
+        self.stderr.borrow_mut().write_all(text.as_bytes())
+    }
+
+    /// Writes a line to stdout for the host, such as a REPL command's result.
+    /// A failure has nowhere better to be reported, so it is ignored.
+    pub fn println(&self, text: &str) {
+        let _ = self.write_stdout(&format!("{}\n", text));
+    }
+
+    /// Writes a line to stderr for the host, such as an error report.
+    pub fn eprintln(&self, text: &str) {
+        let _ = self.write_stderr(&format!("{}\n", text));
+    }
+
+    /// Pushes out anything buffered, such as a prompt without a newline.
+    pub fn flush(&self) -> io::Result<()> {
+        self.stdout.borrow_mut().flush()?;
+        self.stderr.borrow_mut().flush()
+    }
+}
+
+/// An in-memory sink. Clones share the same buffer, so a host can keep one
+/// clone and read what the interpreter wrote to the other.
+#[derive(Debug, Clone, Default)]
+pub struct Buffer {
+    bytes: Rc<RefCell<Vec<u8>>>,
+}
+
+impl Buffer {
+    pub fn new() -> Buffer {
+        Buffer::default()
+    }
+
+    /// Everything written so far, with invalid UTF-8 replaced.
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.bytes.borrow()).into_owned()
+    }
+
+    pub fn clear(&self) {
+        self.bytes.borrow_mut().clear();
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.bytes.borrow_mut().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use std::{cell::RefCell, fmt::Formatter};
+
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::{self, SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::interpreter::{
+    interpreter::Value,
+    map::{HashKey, ValueMap},
+};
+
+/// Lists become sequences, maps become maps in insertion order and nil
+/// becomes unit, which JSON writes as `null`. Functions and errors cannot
+/// be serialized, and neither can a list or map that contains itself.
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let open = RefCell::new(Vec::new());
+        Tracked { value: self, open: &open }.serialize(serializer)
+    }
+}
+
+impl Serialize for HashKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            HashKey::Integer(integer) => serializer.serialize_i64(*integer),
+            HashKey::String(string) => serializer.serialize_str(string),
+            HashKey::Boolean(boolean) => serializer.serialize_bool(*boolean),
+            HashKey::Nil => serializer.serialize_unit(),
+        }
+    }
+}
+
+/// A value being serialized, with the lists and maps that enclose it so
+/// that cycles are reported instead of recursing forever.
+struct Tracked<'a> {
+    value: &'a Value,
+    open: &'a RefCell<Vec<usize>>,
+}
+
+impl Tracked<'_> {
+    fn nested<S: Serializer>(&self, address: usize) -> Result<(), S::Error> {
+        if self.open.borrow().contains(&address) {
+            return Err(ser::Error::custom(format!(
+                "found a {} that contains itself",
+                self.value.type_name()
+            )));
+        }
+
+        self.open.borrow_mut().push(address);
+        Ok(())
+    }
+
+    fn child<'b>(&'b self, value: &'b Value) -> Tracked<'b> {
+        Tracked { value, open: self.open }
+    }
+}
+
+impl Serialize for Tracked<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.value {
+            Value::Integer(integer) => serializer.serialize_i64(*integer),
+            Value::Float(float) => serializer.serialize_f64(*float),
+            Value::String(string) => serializer.serialize_str(string),
+            Value::Boolean(boolean) => serializer.serialize_bool(*boolean),
+            Value::Nil => serializer.serialize_unit(),
+            Value::List(list) => {
+                self.nested::<S>(list.as_ptr() as usize)?;
+
+                let list = list.borrow();
+                let mut seq = serializer.serialize_seq(Some(list.len()))?;
+                for element in list.iter() {
+                    seq.serialize_element(&self.child(element))?;
+                }
+
+                self.open.borrow_mut().pop();
+                seq.end()
+            }
+            Value::Map(map) => {
+                self.nested::<S>(map.as_ptr() as usize)?;
+
+                let map = map.borrow();
+                let mut entries = serializer.serialize_map(Some(map.len()))?;
+                for (key, value) in map.iter() {
+                    entries.serialize_entry(key, &self.child(value))?;
+                }
+
+                self.open.borrow_mut().pop();
+                entries.end()
+            }
+            other => Err(ser::Error::custom(format!(
+                "found a {}, which has no serialized form",
+                other.type_name()
+            ))),
+        }
+    }
+}
+
+/// Builds values from any self-describing format. Unsigned integers too
+/// large for an integer become floats, and map keys must be integers,
+/// strings, booleans or nil.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "a taco value")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Boolean(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Integer(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Value, E> {
+        match i64::try_from(value) {
+            Ok(integer) => Ok(Value::Integer(integer)),
+            Err(_) => Ok(Value::Float(value as f64)),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::Float(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Nil)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(element) = seq.next_element()? {
+            elements.push(element);
+        }
+
+        Ok(Value::list(elements))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Value, A::Error> {
+        let mut map = ValueMap::new();
+        while let Some((key, value)) = access.next_entry::<Value, Value>()? {
+            let key = match HashKey::from_value(&key) {
+                Some(key) => key,
+                None => {
+                    return Err(de::Error::custom(format!(
+                        "a {} cannot be used as a map key",
+                        key.type_name()
+                    )))
+                }
+            };
+
+            map.insert(key, value);
+        }
+
+        Ok(Value::map(map))
+    }
+}
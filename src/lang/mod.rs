@@ -1 +1,2 @@
-pub mod taco;
\ No newline at end of file
+pub mod taco;
+pub mod repl;
\ No newline at end of file
@@ -0,0 +1,363 @@
+use std::{borrow::Cow, fs::read_to_string, ops::ControlFlow, path::Path};
+
+use rustyline::{
+    completion::Completer,
+    highlight::Highlighter,
+    hint::Hinter,
+    validate::{ValidationContext, ValidationResult, Validator},
+    Cmd, ConditionalEventHandler, Context, Event, EventContext, Helper, RepeatCount,
+};
+
+use crate::{
+    core::expression::Expr,
+    error::{
+        handling::error,
+        interpreter::throw_runtime_error,
+        parser::{report_parser_error, ParseErrorKind},
+    },
+    interpreter::output::Output,
+    lang::taco::Taco,
+    parser::parser::Parser,
+    run::parse,
+    syntax::{expression::ExpressionStatement, statement::Statement},
+    token::{scanner::Scanner, tokens::TokenType},
+};
+
+const HELP: &str = "\
+:help          Show this list
+:quit          Leave the REPL
+:reset         Forget everything defined so far
+:env           List top-level bindings and their values
+:load <file>   Run a script in this session
+:type <expr>   Evaluate an expression and show its type
+:tokens <src>  Show the tokens the scanner reads from source
+:ast <expr>    Show the syntax tree of an expression";
+
+/// The prompt for the first line of an entry.
+pub const PROMPT: &str = ">> ";
+
+// rustyline only prompts for the first line of an entry, and places the
+// cursor from the text alone. So continuation lines start with an indent
+// as wide as the continuation prompt, which the highlighter draws over.
+const CONTINUATION: &str = "... ";
+const INDENT: &str = "    ";
+
+const PROMPT_COLOUR: &str = "\x1b[2m";
+const KEYWORD: &str = "\x1b[35m";
+const STRING: &str = "\x1b[32m";
+const NUMBER: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// Line editor hooks for the REPL.
+pub struct ReplHelper {
+    keywords: Vec<String>,
+    /// The global names to complete, which the REPL refreshes before
+    /// reading each input.
+    names: Vec<String>,
+}
+
+impl Default for ReplHelper {
+    fn default() -> Self {
+        ReplHelper::new()
+    }
+}
+
+impl ReplHelper {
+    pub fn new() -> ReplHelper {
+        ReplHelper {
+            keywords: Scanner::create_keywords().into_keys().collect(),
+            names: Vec::new(),
+        }
+    }
+
+    pub fn set_names(&mut self, names: Vec<String>) {
+        self.names = names;
+    }
+}
+
+impl Helper for ReplHelper {}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    /// Completes the word before the cursor to a keyword or global name.
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map_or(pos, |(index, _)| index);
+
+        let word = &line[start..pos];
+        if word.is_empty() {
+            return Ok((pos, Vec::new()));
+        }
+
+        let mut candidates = self
+            .keywords
+            .iter()
+            .chain(&self.names)
+            .filter(|candidate| candidate.starts_with(word))
+            .cloned()
+            .collect::<Vec<String>>();
+
+        candidates.sort();
+        candidates.dedup();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {
+    /// Colours keywords, strings and numbers, as the scanner reads them, and
+    /// draws the continuation prompt over the indent of continuation lines.
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        let mut scanner = Scanner::new(line.to_string());
+        let tokens = scanner.scan_tokens();
+
+        let chars = line.chars().collect::<Vec<char>>();
+        let mut highlighted = String::with_capacity(line.len());
+        let mut cursor = 0;
+        for (token, &(start, end)) in tokens.iter().zip(&scanner.spans) {
+            let colour = match token.get_token_type() {
+                TokenType::String => STRING,
+                TokenType::Integer | TokenType::Float => NUMBER,
+                TokenType::Identifier | TokenType::Eof => continue,
+                _ if scanner.keywords.contains_key(token.get_lexeme()) => KEYWORD,
+                _ => continue,
+            };
+
+            let (start, end) = (start as usize, end as usize);
+            push_between_tokens(&mut highlighted, &chars[cursor..start]);
+            highlighted.push_str(colour);
+            highlighted.extend(&chars[start..end]);
+            highlighted.push_str(RESET);
+            cursor = end;
+        }
+
+        if cursor == 0 && !line.contains(&format!("\n{}", INDENT)) {
+            return Cow::Borrowed(line);
+        }
+
+        push_between_tokens(&mut highlighted, &chars[cursor..]);
+        Cow::Owned(highlighted)
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize) -> bool {
+        true
+    }
+}
+
+/// Appends the text between two tokens, with the continuation prompt in
+/// place of the indent at the start of each continuation line. Newlines in
+/// strings are inside tokens, so their contents are never drawn over.
+fn push_between_tokens(highlighted: &mut String, text: &[char]) {
+    let text = text.iter().collect::<String>();
+    let prompt = format!("\n{}{}{}", PROMPT_COLOUR, CONTINUATION, RESET);
+    highlighted.push_str(&text.replace(&format!("\n{}", INDENT), &prompt));
+}
+
+impl Validator for ReplHelper {
+    /// Keeps the entry open while the input so far cannot be complete.
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        let input = ctx.input();
+        if !input.trim_start().starts_with(':') && is_incomplete(input) {
+            return Ok(ValidationResult::Incomplete);
+        }
+
+        Ok(ValidationResult::Valid(None))
+    }
+}
+
+/// Starts a continuation line with `INDENT` when Enter is pressed at the end
+/// of incomplete input, so the highlighter has room to draw the continuation
+/// prompt. Inside an unfinished string or comment, where spaces would change
+/// what was typed, the validator adds a bare newline instead.
+pub struct ContinuationHandler;
+
+impl ConditionalEventHandler for ContinuationHandler {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        let line = ctx.line();
+        if ctx.pos() != line.len() || line.trim_start().starts_with(':') {
+            return None;
+        }
+
+        let mut scanner = Scanner::new(line.to_string());
+        scanner.scan_tokens();
+        if scanner.unterminated || !is_incomplete(line) {
+            return None;
+        }
+
+        Some(Cmd::Insert(1, format!("\n{}", INDENT)))
+    }
+}
+
+/// Whether `source` stops partway through something more input could
+/// finish: a backtick string or block comment, or a statement or
+/// expression the parser ran out of tokens in. Other errors are left for
+/// the parser to report.
+pub fn is_incomplete(source: &str) -> bool {
+    let mut scanner = Scanner::new(source.to_string());
+    let tokens = scanner.scan_tokens();
+    if scanner.unterminated {
+        return true;
+    }
+    if !scanner.errors.is_empty() {
+        return false;
+    }
+
+    match Parser::new(tokens).parse() {
+        Ok(_) => false,
+        Err(err) => err.get_kind() == ParseErrorKind::UnexpectedEnd,
+    }
+}
+
+/// Runs a REPL command, the input after a leading `:`. Breaks when the
+/// REPL should stop.
+pub fn run_command(command: &str, taco: &mut Taco) -> ControlFlow<()> {
+    let (name, argument) = match command.split_once(char::is_whitespace) {
+        Some((name, argument)) => (name, argument.trim()),
+        None => (command, ""),
+    };
+
+    let output = taco.output();
+    match name {
+        "help" => output.println(HELP),
+        "quit" => return ControlFlow::Break(()),
+        "reset" => taco.interpreter().reset_globals(),
+        "env" => {
+            let interpreter = taco.interpreter();
+            for (name, value) in interpreter.top_level_bindings() {
+                output.println(&format!("{} = {}", name, interpreter.repr(value)));
+            }
+        }
+        "load" => load(argument, taco),
+        "type" => {
+            if let Some(expr) = parse_expression(argument, &output) {
+                let statement = Statement::ExpressionStatement(ExpressionStatement::new(expr));
+                match taco.interpreter().interpret_value(vec![statement]) {
+                    Ok(value) => output.println(value.type_name()),
+                    Err(err) => {
+                        throw_runtime_error(&output, err);
+                    }
+                }
+            }
+        }
+        "tokens" => {
+            let _ = taco.dump_tokens(argument);
+        }
+        "ast" => {
+            if let Some(expr) = parse_expression(argument, &output) {
+                output.println(&format!("{:#?}", expr));
+            }
+        }
+        _ => output.eprintln(&format!("Unknown command ':{}'. Type :help for a list of commands.", name)),
+    }
+
+    ControlFlow::Continue(())
+}
+
+fn load(path: &str, taco: &mut Taco) {
+    let output = taco.output();
+    if path.is_empty() {
+        output.eprintln("Usage: :load <file>");
+        return;
+    }
+
+    let source = match read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            output.eprintln(&format!("Could not read '{}': {}", path, err));
+            return;
+        }
+    };
+
+    // Run it as the main file, as if it had been run directly, so that its
+    // imports resolve relative to it.
+    if let Some(statements) = parse(source, taco) {
+        let result = taco
+            .interpreter()
+            .with_main_file(Path::new(path), |interpreter| interpreter.interpret(Ok(statements)));
+        if let Err(e) = result {
+            throw_runtime_error(&output, e);
+            taco.set_runtime_error(true);
+        }
+    }
+    taco.set_error(false);
+}
+
+/// Parses a command's argument as an expression, reporting any errors on
+/// `output`.
+fn parse_expression(source: &str, output: &Output) -> Option<Expr> {
+    let mut scanner = Scanner::new(source.to_string());
+    let tokens = scanner.scan_tokens();
+    if !scanner.errors.is_empty() {
+        for (line, message) in &scanner.errors {
+            error(output, *line, message);
+        }
+        return None;
+    }
+
+    match Parser::new(tokens).parse_expression() {
+        Ok(expr) => Some(expr),
+        Err(err) => {
+            report_parser_error(output, &err);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_incomplete;
+
+    #[test]
+    fn open_brackets_are_incomplete() {
+        assert!(is_incomplete("taco f() {"));
+        assert!(is_incomplete("let xs = [1,\n2,"));
+        assert!(is_incomplete("print (1 +"));
+    }
+
+    #[test]
+    fn open_strings_and_comments_are_incomplete() {
+        assert!(is_incomplete("let s = `first line"));
+        assert!(is_incomplete("/* a comment"));
+        assert!(!is_incomplete("let s = `one\ntwo`;"));
+        assert!(!is_incomplete("/* done */"));
+    }
+
+    #[test]
+    fn statements_missing_their_body_are_incomplete() {
+        assert!(is_incomplete("if (x)"));
+        assert!(is_incomplete("taco f()"));
+        assert!(is_incomplete("while (true)"));
+        assert!(is_incomplete("let x = 1"));
+    }
+
+    #[test]
+    fn complete_input_is_not_incomplete() {
+        assert!(!is_incomplete(""));
+        assert!(!is_incomplete("print 1;"));
+        assert!(!is_incomplete("taco f() {\n  return 1;\n}"));
+        assert!(!is_incomplete("if (x) { print x; } else { print 0; }"));
+    }
+
+    #[test]
+    fn errors_more_input_cannot_fix_are_not_incomplete() {
+        // The stray `)` is an error whatever follows, so it is reported
+        // straight away rather than waiting for the `{` to be closed.
+        assert!(!is_incomplete(") {"));
+        assert!(!is_incomplete("f()) {"));
+        assert!(!is_incomplete("let = {"));
+        assert!(!is_incomplete("@ {"));
+    }
+}
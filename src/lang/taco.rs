@@ -1,8 +1,25 @@
-use std::fs::read;
+use std::{
+    fs::{create_dir_all, read_to_string},
+    io::{stdin, Read},
+    path::{Path, PathBuf},
+};
 
-use rustyline::{error::ReadlineError, DefaultEditor};
+use rustyline::{
+    error::ReadlineError, history::DefaultHistory, Editor, EventHandler, KeyCode, KeyEvent, Modifiers,
+};
 
-use crate::{interpreter::interpreter::Interpreter, run::exec, parser::parser::Parser};
+use crate::{
+    interpreter::{
+        interpreter::{Interpreter, Value},
+        native::Arity,
+        output::Output,
+    },
+    error::handling::error,
+    lang::repl::{run_command, ContinuationHandler, ReplHelper, PROMPT},
+    parser::parser::Parser,
+    run::{exec, exec_repl, parse},
+    token::scanner::Scanner,
+};
 
 pub struct Taco {
     pub has_error: bool,
@@ -11,6 +28,12 @@ pub struct Taco {
     pub parser: Option<Parser>
 }
 
+impl Default for Taco {
+    fn default() -> Self {
+        Taco::new()
+    }
+}
+
 impl Taco {
     pub fn new() -> Taco {
         Taco {
@@ -23,12 +46,26 @@ impl Taco {
 
     pub fn interpreter(&mut self) -> &mut Interpreter {
         if self.interpreter.is_none() {
-            self.interpreter = Some(Interpreter::new(self));
+            self.interpreter = Some(Interpreter::new());
         }
 
         self.interpreter.as_mut().unwrap()
     }
 
+    /// Where the interpreter's output goes, for reporting errors and
+    /// showing results alongside it.
+    pub fn output(&mut self) -> Output {
+        self.interpreter().get_output().clone()
+    }
+
+    /// Registers a host function that scripts can call by `name`.
+    pub fn define_native<F>(&mut self, name: &str, arity: Arity, function: F)
+    where
+        F: Fn(&mut Interpreter, Vec<Value>) -> Result<Value, String> + 'static,
+    {
+        self.interpreter().define_native(name, arity, function);
+    }
+
     pub fn set_error (&mut self, value: bool) {
         self.has_error = value;
     }
@@ -37,41 +74,156 @@ impl Taco {
         self.has_runtime_error = value;
     }
 
-    pub fn run_file(&mut self, path: String) {
-        let bytes = read(path).unwrap();
+    /// Reads the script at `path`, or standard input if `path` is `-`. If
+    /// it cannot be read, reports why and returns exit code 66.
+    pub fn read_source(&mut self, path: &str) -> Result<String, i32> {
+        let read = if path == "-" {
+            let mut source = String::new();
+            stdin().read_to_string(&mut source).map(|_| source)
+        } else {
+            read_to_string(path)
+        };
 
-        let source = String::from_utf8(bytes).unwrap();
+        read.map_err(|err| {
+            self.output().eprintln(&format!("Could not read '{}': {}", path, err));
+            66
+        })
+    }
 
+    /// Runs the script at `path`, or standard input if `path` is `-`,
+    /// reporting errors on stderr. On failure, returns the exit code the
+    /// process should end with: 66 if the file cannot be read, 65 for a
+    /// syntax error and 70 for a runtime error.
+    pub fn run_file(&mut self, path: String) -> Result<(), i32> {
+        let source = self.read_source(&path)?;
+        self.run_script(&path, source)
+    }
+
+    /// Runs `source`, already read from `path`, so that its imports
+    /// resolve relative to that file.
+    pub fn run_script(&mut self, path: &str, source: String) -> Result<(), i32> {
+        if path != "-" {
+            self.interpreter().set_main_file(Path::new(path));
+        }
+
+        self.run_source(source)
+    }
+
+    /// Runs a script that did not come from a file, failing like
+    /// `run_file`.
+    pub fn run_source(&mut self, source: String) -> Result<(), i32> {
         exec(source, self);
 
         if self.has_error {
-            std::process::exit(65);
+            return Err(65);
         }
         if self.has_runtime_error {
-            std::process::exit(70);
+            return Err(70);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `source` scans and parses without running it, returning
+    /// exit code 65 if it does not.
+    pub fn check(&mut self, source: String) -> Result<(), i32> {
+        match parse(source, self) {
+            Some(_) => Ok(()),
+            None => Err(65),
+        }
+    }
+
+    /// Prints the tokens the scanner reads from `source`, one per line.
+    pub fn dump_tokens(&mut self, source: &str) -> Result<(), i32> {
+        let output = self.output();
+        let mut scanner = Scanner::new(source.to_string());
+        for token in scanner.scan_tokens() {
+            output.println(&token.to_string());
         }
 
-        // todo!("Remove the code splurging (in `exec`) and handle the error better");
+        for (line, message) in &scanner.errors {
+            error(&output, *line, message);
+        }
+
+        if !scanner.errors.is_empty() {
+            return Err(65);
+        }
+
+        Ok(())
+    }
+
+    /// Prints the syntax tree of each top-level statement in `source`.
+    pub fn dump_ast(&mut self, source: &str) -> Result<(), i32> {
+        let statements = parse(source.to_string(), self).ok_or(65)?;
+        let output = self.output();
+        for statement in statements {
+            output.println(&format!("{:#?}", statement));
+        }
+
+        Ok(())
     }
 
     pub fn run_repl(&mut self) {
-        let mut rl = DefaultEditor::new().unwrap();
+        let mut rl = Editor::<ReplHelper, DefaultHistory>::new().unwrap();
+        rl.set_helper(Some(ReplHelper::new()));
+        rl.bind_sequence(
+            KeyEvent(KeyCode::Enter, Modifiers::NONE),
+            EventHandler::Conditional(Box::new(ContinuationHandler)),
+        );
+
+        let history = history_path();
+        if let Some(path) = &history {
+            // There is no history yet the first time the REPL runs.
+            let _ = rl.load_history(path);
+        }
 
         // Used to create an infinite loop
         loop {
-            let readline = rl.readline(">> ");
-            match readline {
+            let names = self.interpreter().global_names();
+            if let Some(helper) = rl.helper_mut() {
+                helper.set_names(names);
+            }
+
+            match rl.readline(PROMPT) {
                 Ok(line) => {
-                    exec(line, self);
+                    let _ = rl.add_history_entry(line.as_str());
+
+                    // Commands start with a colon, which no statement can.
+                    if let Some(command) = line.trim().strip_prefix(':') {
+                        if run_command(command, self).is_break() {
+                            break;
+                        }
+                        continue;
+                    }
+
+                    exec_repl(line, self);
                     self.has_error = false;
                 }
                 Err(ReadlineError::Interrupted) => break,
                 Err(ReadlineError::Eof) => break,
                 Err(err) => {
-                    eprintln!("Error occured: {}", err);
+                    self.output().eprintln(&format!("Error occured: {}", err));
                     break;
                 }
             }
         }
+
+        if let Some(path) = &history {
+            let saved = match path.parent() {
+                Some(dir) => create_dir_all(dir).map_err(ReadlineError::from),
+                None => Ok(()),
+            }
+            .and_then(|_| rl.save_history(path));
+
+            if let Err(err) = saved {
+                self.output().eprintln(&format!("Could not save history to '{}': {}", path.display(), err));
+            }
+        }
     }
 }
+
+/// Where the REPL keeps its history between sessions, inside the user's
+/// data directory.
+fn history_path() -> Option<PathBuf> {
+    dirs_next::data_dir().map(|dir| dir.join("taco").join("history.txt"))
+}
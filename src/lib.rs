@@ -0,0 +1,40 @@
+//! The taco language as a library. `Engine` is the entry point for
+//! embedding taco in a Rust program; the `taco` binary is built on `Taco`.
+//!
+//! Only the embedding API is documented. The modules marked hidden are
+//! public for the binary and the tests, and may change at any time.
+
+#[macro_use]
+#[doc(hidden)]
+pub mod parser;
+#[doc(hidden)]
+pub mod lang;
+#[doc(hidden)]
+pub mod token;
+pub mod error;
+#[doc(hidden)]
+pub mod run;
+#[doc(hidden)]
+pub mod core;
+pub mod interpreter;
+pub(crate) mod environment;
+pub(crate) mod util;
+#[doc(hidden)]
+pub mod syntax;
+pub(crate) mod stdlib;
+pub(crate) mod vm;
+#[doc(hidden)]
+pub mod optimizer;
+pub mod engine;
+
+pub use crate::{
+    engine::engine::Engine,
+    error::engine::Error,
+    interpreter::{
+        convert::{ConversionError, FromValue, IntoValue},
+        interpreter::{Backend, Value},
+        limits::Limits,
+        map::{HashKey, ValueMap},
+        native::Arity,
+    },
+};
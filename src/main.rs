@@ -1,27 +1,298 @@
-#[macro_use]
-mod parser;
-mod lang;
-mod token;
-mod error;
-mod run;
-mod core;
-mod interpreter;
-mod environment;
-mod util;
-mod syntax;
-
-use crate::lang::taco::Taco;
+use taco::{
+    interpreter::interpreter::{Backend, Value, DEFAULT_MAX_DEPTH},
+    lang::taco::Taco,
+};
 
-fn main() {
-    let mut args = std::env::args();
+const USAGE: &str = "\
+Usage: taco [options]                         Start the REPL
+       taco [options] [run] <file> [args...]  Run a script, or stdin if <file> is -
+       taco [options] -e <code> [args...]     Run code given on the command line
+       taco [options] check <file>            Check that a script parses without running it
+
+Options:
+  -O, --optimize         Fold constants before running
+  --backend tree|vm      Choose the tree-walker or the bytecode VM
+  --max-depth <n>        Allow at most <n> nested calls
+  --dump-tokens          Print the script's tokens instead of running it
+  --dump-ast             Print the script's syntax tree instead of running it
+  -h, --help             Show this message
+  --version              Show the version
+
+Options may come before or after run and check, up to the script. Everything
+after the script, or after --, is passed to it as the list `args`. With -e the
+code is the script, so a file named after it is an argument and is not run.";
+
+enum Input {
+    /// A path, or `-` for stdin.
+    File(String),
+    Code(String),
+}
+
+enum Command {
+    Repl,
+    Run(Input, Vec<String>),
+    Check(Input),
+}
+
+struct Options {
+    max_depth: usize,
+    backend: Backend,
+    optimize: bool,
+    dump_tokens: bool,
+    dump_ast: bool,
+    command: Command,
+}
+
+/// Reads the command line. `Err` carries the exit code, after any message
+/// has been printed.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, i32> {
+    let mut options = Options {
+        max_depth: DEFAULT_MAX_DEPTH,
+        backend: Backend::Tree,
+        optimize: false,
+        dump_tokens: false,
+        dump_ast: false,
+        command: Command::Repl,
+    };
+
+    // Set by `run` or `check`, which may only come before the script.
+    let mut subcommand = None;
+    let mut input = None;
+    let mut script_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-O" | "--optimize" => options.optimize = true,
+            "--dump-tokens" => options.dump_tokens = true,
+            "--dump-ast" => options.dump_ast = true,
+            "--backend" => match args.next().and_then(|name| Backend::from_name(&name)) {
+                Some(backend) => options.backend = backend,
+                None => return Err(usage_error("--backend expects 'tree' or 'vm'.")),
+            },
+            "--max-depth" => match args.next().and_then(|depth| depth.parse::<usize>().ok()) {
+                Some(depth) => options.max_depth = depth,
+                None => return Err(usage_error("--max-depth expects a non-negative integer.")),
+            },
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Err(0);
+            }
+            "--version" => {
+                println!("taco {}", env!("CARGO_PKG_VERSION"));
+                return Err(0);
+            }
+            "-e" => {
+                let code = args.next().ok_or_else(|| usage_error("-e expects code to run."))?;
+                if input.is_some() {
+                    return Err(usage_error("-e may only be given once."));
+                }
+                input = Some(Input::Code(code));
+            }
+            "--" => {
+                if input.is_none() {
+                    input = args.next().map(Input::File);
+                }
+                break;
+            }
+            "run" | "check" if subcommand.is_none() && input.is_none() => subcommand = Some(arg),
+            _ if arg != "-" && arg.starts_with('-') => {
+                return Err(usage_error(&format!("Unknown option '{}'.", arg)));
+            }
+            // The first argument that is not an option is the script, unless
+            // `-e` gave one, and everything after the script is for it.
+            _ => {
+                match input {
+                    Some(_) => script_args.push(arg),
+                    None => input = Some(Input::File(arg)),
+                }
+                break;
+            }
+        }
+    }
+    script_args.extend(args);
+
+    options.command = match (subcommand.as_deref(), input) {
+        (Some(name), None) => return Err(usage_error(&format!("{} expects a file.", name))),
+        (None, None) => Command::Repl,
+        (Some("check"), Some(input)) => match script_args.first() {
+            Some(extra) => return Err(usage_error(&format!("Unexpected argument '{}'.", extra))),
+            None => Command::Check(input),
+        },
+        (_, Some(input)) => Command::Run(input, script_args),
+    };
+
+    if let Command::Repl = options.command {
+        if options.dump_tokens || options.dump_ast {
+            return Err(usage_error("--dump-tokens and --dump-ast need a script."));
+        }
+    }
+
+    Ok(options)
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    64
+}
+
+fn run(options: Options) -> Result<(), i32> {
     let mut lang = Taco::new();
+    lang.interpreter().set_max_depth(options.max_depth);
+    lang.interpreter().set_backend(options.backend);
+    lang.interpreter().set_optimize(options.optimize);
+
+    let (input, args) = match options.command {
+        Command::Repl => {
+            lang.run_repl();
+            lang.output().println("Done!");
+            return Ok(());
+        }
+        Command::Run(input, args) => (input, Some(args)),
+        Command::Check(input) => (input, None),
+    };
+
+    let source = match &input {
+        Input::File(path) => lang.read_source(path)?,
+        Input::Code(code) => code.clone(),
+    };
+
+    if options.dump_tokens || options.dump_ast {
+        if options.dump_tokens {
+            lang.dump_tokens(&source)?;
+        }
+        if options.dump_ast {
+            lang.dump_ast(&source)?;
+        }
+        return Ok(());
+    }
+
+    let args = match args {
+        Some(args) => args,
+        None => return lang.check(source),
+    };
+
+    lang.interpreter().define_global("args", Value::from(args));
+    match input {
+        Input::File(path) => lang.run_script(&path, source),
+        Input::Code(_) => lang.run_source(source),
+    }
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(code) => std::process::exit(code),
+    };
+
+    if let Err(code) = run(options) {
+        std::process::exit(code);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, i32> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn run_command(args: &[&str]) -> (Options, String, Vec<String>) {
+        let mut options = parse(args).unwrap_or_else(|code| panic!("{:?} failed with {}", args, code));
+        match std::mem::replace(&mut options.command, Command::Repl) {
+            Command::Run(Input::File(path), script_args) => (options, path, script_args),
+            Command::Run(Input::Code(code), script_args) => (options, code, script_args),
+            _ => panic!("{:?} did not parse as a run", args),
+        }
+    }
+
+    #[test]
+    fn no_arguments_start_the_repl() {
+        assert!(matches!(parse(&[]).unwrap().command, Command::Repl));
+        assert!(matches!(parse(&["-O", "--"]).unwrap().command, Command::Repl));
+    }
+
+    #[test]
+    fn options_may_follow_run() {
+        let (options, path, args) = run_command(&["run", "--backend", "vm", "-O", "main.taco", "a"]);
+        assert_eq!(options.backend, Backend::Vm);
+        assert!(options.optimize);
+        assert_eq!(path, "main.taco");
+        assert_eq!(args, ["a"]);
+    }
+
+    #[test]
+    fn options_may_precede_run() {
+        let (options, path, _) = run_command(&["--max-depth", "10", "run", "main.taco"]);
+        assert_eq!(options.max_depth, 10);
+        assert_eq!(path, "main.taco");
+    }
+
+    #[test]
+    fn arguments_after_the_script_are_the_scripts() {
+        let (options, path, args) = run_command(&["main.taco", "--max-depth", "10", "-O"]);
+        assert_eq!(options.max_depth, DEFAULT_MAX_DEPTH);
+        assert!(!options.optimize);
+        assert_eq!(path, "main.taco");
+        assert_eq!(args, ["--max-depth", "10", "-O"]);
+    }
+
+    #[test]
+    fn options_may_follow_code() {
+        let (options, code, args) = run_command(&["-e", "print args;", "--backend", "vm", "x", "-O"]);
+        assert_eq!(options.backend, Backend::Vm);
+        assert!(!options.optimize);
+        assert_eq!(code, "print args;");
+        assert_eq!(args, ["x", "-O"]);
+    }
+
+    #[test]
+    fn files_after_code_are_arguments() {
+        let (_, code, args) = run_command(&["-e", "print args;", "script.taco"]);
+        assert_eq!(code, "print args;");
+        assert_eq!(args, ["script.taco"]);
+    }
+
+    #[test]
+    fn double_dash_ends_the_options() {
+        let (_, path, args) = run_command(&["--", "-odd.taco", "--help"]);
+        assert_eq!(path, "-odd.taco");
+        assert_eq!(args, ["--help"]);
+
+        let (_, code, args) = run_command(&["-e", "1;", "--", "--backend"]);
+        assert_eq!(code, "1;");
+        assert_eq!(args, ["--backend"]);
+
+        let (_, path, _) = run_command(&["run", "--", "run"]);
+        assert_eq!(path, "run");
+    }
+
+    #[test]
+    fn dash_is_stdin() {
+        let (_, path, args) = run_command(&["-", "a"]);
+        assert_eq!(path, "-");
+        assert_eq!(args, ["a"]);
+    }
+
+    #[test]
+    fn check_takes_options_and_one_file() {
+        let options = parse(&["check", "-O", "main.taco"]).unwrap();
+        assert!(options.optimize);
+        assert!(matches!(options.command, Command::Check(Input::File(ref path)) if path == "main.taco"));
+
+        assert_eq!(parse(&["check", "main.taco", "extra"]).err(), Some(64));
+    }
 
-    if args.len() > 2 {
-        println!("Usage: taco [file]");
-    } else if args.len() == 2 {
-        lang.run_file(args.nth(1).unwrap());
-    } else {
-        lang.run_repl();
-        println!("Done!");
+    #[test]
+    fn mistakes_are_usage_errors() {
+        assert_eq!(parse(&["run"]).err(), Some(64));
+        assert_eq!(parse(&["check", "-O"]).err(), Some(64));
+        assert_eq!(parse(&["--bogus", "main.taco"]).err(), Some(64));
+        assert_eq!(parse(&["--backend", "jit"]).err(), Some(64));
+        assert_eq!(parse(&["--dump-ast"]).err(), Some(64));
+        assert_eq!(parse(&["-e"]).err(), Some(64));
+        assert_eq!(parse(&["-e", "print 1;", "-e", "print 2;"]).err(), Some(64));
+        assert_eq!(parse(&["-e", "print 1;", "-O", "-e", "print 2;"]).err(), Some(64));
     }
 }
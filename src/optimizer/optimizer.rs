@@ -0,0 +1,234 @@
+use std::rc::Rc;
+
+use crate::{
+    core::{
+        assign::AssignExpr, binary::BinaryExpr, call::CallExpr, expression::Expr, get::GetExpr,
+        index::IndexExpr, list::ListExpr, literal::Literal, logical::LogicalExpr, map::MapExpr,
+        set_index::SetIndexExpr, slice::SliceExpr, unary::UnaryExpr,
+    },
+    interpreter::{interpreter::Value, operators},
+    syntax::{
+        block::BlockStatement,
+        export::ExportStatement,
+        expression::ExpressionStatement,
+        function::FunctionStatement,
+        print::PrintStatement,
+        r#if::IfStatement,
+        r#let::LetStatement,
+        r#return::ReturnStatement,
+        r#try::{CatchClause, TryStatement},
+        r#while::WhileStatement,
+        statement::Statement,
+        throw::ThrowStatement,
+    },
+    token::tokens::TokenType,
+};
+
+/// Simplifies a program before it runs.
+///
+/// Operators whose operands are all literals are computed once, using the
+/// same code the interpreter runs, and branches whose condition is a
+/// literal are resolved. An operation that would fail at runtime, such as
+/// `1 / 0`, is left in place so the error is still raised when it runs.
+pub fn optimize(statements: Vec<Statement>) -> Vec<Statement> {
+    statements.into_iter().filter_map(optimize_statement).collect()
+}
+
+/// Returns `None` when the statement can never do anything.
+fn optimize_statement(statement: Statement) -> Option<Statement> {
+    let statement = match statement {
+        Statement::ExpressionStatement(statement) => Statement::ExpressionStatement(
+            ExpressionStatement::new(optimize_expression(statement.expression)),
+        ),
+        Statement::PrintStatement(statement) => {
+            Statement::PrintStatement(PrintStatement::new(optimize_expression(statement.expression)))
+        }
+        Statement::LetStatement(declaration) => Statement::LetStatement(LetStatement::new(
+            declaration.name,
+            optimize_expression(declaration.initializer),
+        )),
+        Statement::BlockStatement(block) => {
+            Statement::BlockStatement(BlockStatement::new(optimize(block.statements)))
+        }
+        Statement::ExportStatement(export) => {
+            let declaration = optimize_statement(*export.declaration)?;
+
+            Statement::ExportStatement(ExportStatement::new(export.keyword, declaration))
+        }
+        Statement::ThrowStatement(throw) => {
+            Statement::ThrowStatement(ThrowStatement::new(throw.keyword, optimize_expression(throw.value)))
+        }
+        Statement::TryStatement(statement) => Statement::TryStatement(TryStatement::new(
+            optimize(statement.body),
+            statement.catch.map(|catch| CatchClause {
+                name: catch.name,
+                body: optimize(catch.body),
+            }),
+            statement.finally.map(optimize),
+        )),
+        Statement::FunctionStatement(declaration) => {
+            let body = Rc::try_unwrap(declaration.body).unwrap_or_else(|body| (*body).clone());
+            Statement::FunctionStatement(FunctionStatement::new(
+                declaration.name,
+                declaration.params,
+                optimize(body),
+            ))
+        }
+        Statement::ReturnStatement(statement) => Statement::ReturnStatement(ReturnStatement::new(
+            statement.keyword,
+            statement.value.map(optimize_expression),
+        )),
+        Statement::IfStatement(branch) => {
+            let condition = optimize_expression(branch.condition);
+
+            // Only the branch that would be taken is kept. A branch that is
+            // not a block runs in the enclosing scope either way.
+            if let Expr::Literal(literal) = &condition {
+                return match Value::from(literal.clone()).is_truthy() {
+                    true => optimize_statement(*branch.then_branch),
+                    false => branch.else_branch.and_then(|other| optimize_statement(*other)),
+                };
+            }
+
+            Statement::IfStatement(IfStatement::new(
+                condition,
+                optimize_statement(*branch.then_branch).unwrap_or_else(empty),
+                branch.else_branch.and_then(|other| optimize_statement(*other)),
+            ))
+        }
+        Statement::WhileStatement(repeat) => {
+            let condition = optimize_expression(repeat.condition);
+
+            if let Expr::Literal(literal) = &condition {
+                if !Value::from(literal.clone()).is_truthy() {
+                    return None;
+                }
+            }
+
+            Statement::WhileStatement(WhileStatement::new(
+                condition,
+                optimize_statement(*repeat.body).unwrap_or_else(empty),
+            ))
+        }
+        statement => statement,
+    };
+
+    Some(statement)
+}
+
+/// A statement that does nothing, for places that need one.
+fn empty() -> Statement {
+    Statement::BlockStatement(BlockStatement::new(Vec::new()))
+}
+
+fn optimize_expression(expr: Expr) -> Expr {
+    match expr {
+        Expr::GroupingExpr(grouping) => optimize_expression(grouping.expr),
+        Expr::UnaryExpr(unary) => {
+            let right = optimize_expression(unary.get_right().clone());
+
+            if let Expr::Literal(literal) = &right {
+                if let Ok(value) = operators::unary(unary.get_operator(), Value::from(literal.clone())) {
+                    if let Some(literal) = to_literal(value) {
+                        return Expr::Literal(literal);
+                    }
+                }
+            }
+
+            UnaryExpr::new(unary.get_operator().clone(), right).into()
+        }
+        Expr::BinaryExpr(binary) => {
+            let left = optimize_expression(binary.get_left().clone());
+            let right = optimize_expression(binary.get_right().clone());
+
+            if let (Expr::Literal(left), Expr::Literal(right)) = (&left, &right) {
+                let folded = operators::binary(
+                    binary.get_operator(),
+                    Value::from(left.clone()),
+                    Value::from(right.clone()),
+                );
+
+                if let Some(literal) = folded.ok().and_then(to_literal) {
+                    return Expr::Literal(literal);
+                }
+            }
+
+            BinaryExpr::new(left, binary.get_operator().clone(), right).into()
+        }
+        Expr::LogicalExpr(logical) => {
+            let left = optimize_expression(logical.get_left().clone());
+            let right = optimize_expression(logical.get_right().clone());
+
+            if let Expr::Literal(literal) = &left {
+                let truthy = Value::from(literal.clone()).is_truthy();
+                let decided = match logical.get_operator().token_type {
+                    TokenType::Or => truthy,
+                    _ => !truthy,
+                };
+
+                return if decided { left } else { right };
+            }
+
+            LogicalExpr::new(left, logical.get_operator().clone(), right).into()
+        }
+        Expr::AssignExpr(assign) => {
+            AssignExpr::new(assign.get_name().clone(), optimize_expression(assign.get_value().clone())).into()
+        }
+        Expr::MapExpr(map) => {
+            let entries = map
+                .get_entries()
+                .iter()
+                .map(|(key, value)| (optimize_expression(key.clone()), optimize_expression(value.clone())))
+                .collect();
+
+            MapExpr::new(map.get_brace().clone(), entries).into()
+        }
+        Expr::IndexExpr(index) => IndexExpr::new(
+            optimize_expression(index.get_object().clone()),
+            index.get_bracket().clone(),
+            optimize_expression(index.get_index().clone()),
+        )
+        .into(),
+        Expr::SetIndexExpr(set) => SetIndexExpr::new(
+            optimize_expression(set.get_object().clone()),
+            set.get_bracket().clone(),
+            optimize_expression(set.get_index().clone()),
+            optimize_expression(set.get_value().clone()),
+        )
+        .into(),
+        Expr::CallExpr(call) => CallExpr::new(
+            optimize_expression(call.get_callee().clone()),
+            call.get_paren().clone(),
+            call.get_arguments().iter().cloned().map(optimize_expression).collect(),
+        )
+        .into(),
+        Expr::GetExpr(get) => {
+            GetExpr::new(optimize_expression(get.get_object().clone()), get.get_name().clone()).into()
+        }
+        Expr::ListExpr(list) => ListExpr::new(
+            list.get_bracket().clone(),
+            list.get_elements().iter().cloned().map(optimize_expression).collect(),
+        )
+        .into(),
+        Expr::SliceExpr(slice) => SliceExpr::new(
+            optimize_expression(slice.get_object().clone()),
+            slice.get_bracket().clone(),
+            slice.get_start().cloned().map(optimize_expression),
+            slice.get_end().cloned().map(optimize_expression),
+        )
+        .into(),
+        expr => expr,
+    }
+}
+
+/// The literal for a folded value, if it has one.
+fn to_literal(value: Value) -> Option<Literal> {
+    match value {
+        Value::Integer(integer) => Some(Literal::Integer(integer)),
+        Value::Float(float) => Some(Literal::Float(float)),
+        Value::String(string) => Some(Literal::String(string)),
+        Value::Boolean(boolean) => Some(Literal::Boolean(boolean)),
+        Value::Nil => Some(Literal::Nil),
+        _ => None,
+    }
+}
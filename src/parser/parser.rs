@@ -1,8 +1,26 @@
+use crate::core::assign::AssignExpr;
+use crate::core::call::CallExpr;
+use crate::core::get::GetExpr;
 use crate::core::grouping::GroupingExpr;
+use crate::core::index::IndexExpr;
+use crate::core::list::ListExpr;
 use crate::core::literal::Literal;
-use crate::error::parser::{throw_error, ParserError};
+use crate::core::logical::LogicalExpr;
+use crate::core::map::MapExpr;
+use crate::core::set_index::SetIndexExpr;
+use crate::core::slice::SliceExpr;
+use crate::error::parser::{throw_error, ParseErrorKind, ParserError};
+use crate::syntax::block::BlockStatement;
+use crate::syntax::export::ExportStatement;
 use crate::syntax::expression::ExpressionStatement;
+use crate::syntax::function::FunctionStatement;
+use crate::syntax::r#if::IfStatement;
+use crate::syntax::import::ImportStatement;
 use crate::syntax::print::PrintStatement;
+use crate::syntax::r#return::ReturnStatement;
+use crate::syntax::r#try::{CatchClause, TryStatement};
+use crate::syntax::throw::ThrowStatement;
+use crate::syntax::r#while::WhileStatement;
 use crate::syntax::r#let::LetStatement;
 use crate::syntax::statement::Statement;
 use crate::{
@@ -11,11 +29,12 @@ use crate::{
 };
 
 #[macro_export]
+#[doc(hidden)]
 macro_rules! into_expr {
     ($id:ident) => {
-        impl Into<Expr> for $id {
-            fn into(self) -> Expr {
-                Expr::$id(Box::new(self))
+        impl From<$id> for Expr {
+            fn from(expr: $id) -> Expr {
+                Expr::$id(Box::new(expr))
             }
         }
     };
@@ -24,13 +43,16 @@ macro_rules! into_expr {
 pub struct Parser {
     pub tokens: Vec<Token>,
     current: u32,
+    // How many function bodies enclose the current token.
+    function_depth: u32,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Parser {
         Parser {
-            tokens: tokens,
+            tokens,
             current: 0,
+            function_depth: 0,
         }
     }
 
@@ -38,74 +60,388 @@ impl Parser {
         let mut statements = Vec::<Statement>::new();
 
         while !self.is_at_end() {
-            let dec = match self.declaration() {
+            let dec = match self.top_level_declaration() {
                 Ok(dec) => dec,
                 Err(err) => {
+                    let err = self.classify(err);
                     self.synchronize();
-                    return Err(ParserError::new(err.token, err.message.as_str()));
+                    return Err(err);
                 }
             };
 
             statements.push(dec);
         }
 
-        return Ok(statements);
+        Ok(statements)
+    }
+
+    /// Parses the tokens as one expression with nothing after it, and no
+    /// trailing semicolon.
+    pub fn parse_expression(&mut self) -> Result<Expr, ParserError> {
+        let expr = self.expression().map_err(|err| self.classify(err))?;
+        if !self.is_at_end() {
+            return Err(throw_error(self.peek(), "Expect end of expression."));
+        }
+
+        Ok(expr)
+    }
+
+    /// Marks an error raised after running out of tokens as an unexpected
+    /// end of input, which more input could fix.
+    fn classify(&mut self, err: ParserError) -> ParserError {
+        match self.is_at_end() {
+            true => err.with_kind(ParseErrorKind::UnexpectedEnd),
+            false => err,
+        }
+    }
+
+    fn top_level_declaration(&mut self) -> Result<Statement, ParserError> {
+        if self.match_tokens(&[TokenType::Export]) {
+            let keyword = self.previous();
+
+            let declaration = if self.match_tokens(&[TokenType::Let]) {
+                self.let_declaration()
+            } else if self.match_tokens(&[TokenType::Func]) {
+                self.function_declaration()
+            } else {
+                return Err(throw_error(self.peek(), "Expect declaration after 'export'."));
+            };
+
+            return match declaration {
+                Ok(declaration) => Ok(Statement::ExportStatement(ExportStatement::new(
+                    keyword,
+                    declaration,
+                ))),
+                Err(err) => Err(err),
+            };
+        }
+
+        self.declaration()
     }
 
     fn declaration(&mut self) -> Result<Statement, ParserError> {
+        if self.check(TokenType::Export) {
+            return Err(throw_error(
+                self.peek(),
+                "Exports are only allowed at the top level of a module.",
+            ));
+        }
+
+        if self.match_tokens(&[TokenType::Import]) {
+            return self.import_statement();
+        }
+
         if self.match_tokens(&[TokenType::Let]) {
             return self.let_declaration();
         }
 
+        if self.match_tokens(&[TokenType::Func]) {
+            return self.function_declaration();
+        }
+
+        if self.match_tokens(&[TokenType::Return]) {
+            return self.return_statement();
+        }
+
         if self.match_tokens(&[TokenType::Print]) {
             return self.print_statement();
         }
 
-        return self.expr_statement();
+        if self.match_tokens(&[TokenType::If]) {
+            return self.if_statement();
+        }
+
+        if self.match_tokens(&[TokenType::While]) {
+            return self.while_statement();
+        }
+
+        if self.match_tokens(&[TokenType::Throw]) {
+            return self.throw_statement();
+        }
+
+        if self.match_tokens(&[TokenType::Try]) {
+            return self.try_statement();
+        }
+
+        // A `{` at the start of a statement opens a block unless it is
+        // clearly the start of a map literal, i.e. `{ key: ...`.
+        if self.check(TokenType::LeftBrace) && !self.is_map_literal() {
+            self.advance();
+            return self.block_statement();
+        }
+
+        self.expr_statement()
     }
 
-    fn let_declaration(&mut self) -> Result<Statement, ParserError> {
-        let name = match self.consume(TokenType::Identifier, "Expect variable name.") {
-            Ok(name) => name,
-            Err(err) => return Err(err),
+    fn is_map_literal(&mut self) -> bool {
+        let key = match self.tokens.get(self.current as usize + 1) {
+            Some(token) => token.token_type,
+            None => return false,
+        };
+        let colon = match self.tokens.get(self.current as usize + 2) {
+            Some(token) => token.token_type,
+            None => return false,
         };
 
+        let is_key = matches!(
+            key,
+            TokenType::Identifier
+                | TokenType::String
+                | TokenType::Integer
+                | TokenType::True
+                | TokenType::False
+                | TokenType::Nil
+        );
+
+        is_key && colon == TokenType::Colon
+    }
+
+    fn block_statement(&mut self) -> Result<Statement, ParserError> {
+        match self.block() {
+            Ok(statements) => Ok(Statement::BlockStatement(BlockStatement::new(statements))),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Parses the statements of a block whose `{` has already been consumed.
+    fn block(&mut self) -> Result<Vec<Statement>, ParserError> {
+        let mut statements = Vec::<Statement>::new();
+
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            let dec = self.declaration()?;
+
+            statements.push(dec);
+        }
+
+        match self.consume(TokenType::RightBrace, "Expect '}' after block.") {
+            Ok(_) => Ok(statements),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn throw_statement(&mut self) -> Result<Statement, ParserError> {
+        let keyword = self.previous();
+        let value = self.expression()?;
+
+        match self.consume(TokenType::Semicolon, "Expect ';' after thrown value.") {
+            Ok(_) => Ok(Statement::ThrowStatement(ThrowStatement::new(keyword, value))),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn try_statement(&mut self) -> Result<Statement, ParserError> {
+        self.consume(TokenType::LeftBrace, "Expect '{' after 'try'.")?;
+        let body = self.block()?;
+
+        let mut catch = None;
+        if self.match_tokens(&[TokenType::Catch]) {
+            let mut name = None;
+
+            if self.match_tokens(&[TokenType::LeftParen]) {
+                name = Some(self.consume(TokenType::Identifier, "Expect name of caught error.")?);
+
+                self.consume(TokenType::RightParen, "Expect ')' after caught error name.")?;
+            }
+
+            self.consume(TokenType::LeftBrace, "Expect '{' after 'catch'.")?;
+
+            match self.block() {
+                Ok(body) => catch = Some(CatchClause { name, body }),
+                Err(err) => return Err(err),
+            }
+        }
+
+        let mut finally = None;
+        if self.match_tokens(&[TokenType::Finally]) {
+            self.consume(TokenType::LeftBrace, "Expect '{' after 'finally'.")?;
+
+            match self.block() {
+                Ok(body) => finally = Some(body),
+                Err(err) => return Err(err),
+            }
+        }
+
+        if catch.is_none() && finally.is_none() {
+            return Err(throw_error(self.peek(), "Expect 'catch' or 'finally' after try block."));
+        }
+
+        Ok(Statement::TryStatement(TryStatement::new(body, catch, finally)))
+    }
+
+    fn let_declaration(&mut self) -> Result<Statement, ParserError> {
+        let name = self.consume(TokenType::Identifier, "Expect variable name.")?;
+
         let mut initializer = Expr::Literal(Literal::Nil);
 
         if self.match_tokens(&[TokenType::Equal]) {
-            let value = match self.expression() {
-                Ok(expr) => expr,
-                Err(err) => return Err(err),
-            };
+            let value = self.expression()?;
 
-            initializer = value.clone().into();
+            initializer = value;
         }
 
         if self.match_tokens(&[TokenType::Semicolon]) {
             return Ok(Statement::LetStatement(LetStatement::new(name, initializer)));
         }
 
-        return Err(throw_error(self.previous(), "Expect ';' after variable declaration."));
+        Err(throw_error(self.previous(), "Expect ';' after variable declaration."))
     }
 
-    fn print_statement(&mut self) -> Result<Statement, ParserError> {
-        let value = match self.expression() {
-            Ok(expr) => expr,
-            Err(err) => return Err(err),
+    fn function_declaration(&mut self) -> Result<Statement, ParserError> {
+        let name = self.consume(TokenType::Identifier, "Expect function name.")?;
+
+        self.consume(TokenType::LeftParen, "Expect '(' after function name.")?;
+
+        let mut params = Vec::<Token>::new();
+        if !self.check(TokenType::RightParen) {
+            loop {
+                match self.consume(TokenType::Identifier, "Expect parameter name.") {
+                    Ok(param) => params.push(param),
+                    Err(err) => return Err(err),
+                }
+
+                if !self.match_tokens(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(TokenType::RightParen, "Expect ')' after parameters.")?;
+
+        self.consume(TokenType::LeftBrace, "Expect '{' before function body.")?;
+
+        self.function_depth += 1;
+        let body = self.block();
+        self.function_depth -= 1;
+
+        match body {
+            Ok(body) => Ok(Statement::FunctionStatement(FunctionStatement::new(
+                name, params, body,
+            ))),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn return_statement(&mut self) -> Result<Statement, ParserError> {
+        let keyword = self.previous();
+
+        if self.function_depth == 0 {
+            return Err(throw_error(keyword, "Can't return from top-level code."));
+        }
+
+        let mut value = None;
+        if !self.check(TokenType::Semicolon) {
+            match self.expression() {
+                Ok(expr) => value = Some(expr),
+                Err(err) => return Err(err),
+            }
+        }
+
+        match self.consume(TokenType::Semicolon, "Expect ';' after return value.") {
+            Ok(_) => Ok(Statement::ReturnStatement(ReturnStatement::new(keyword, value))),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn import_statement(&mut self) -> Result<Statement, ParserError> {
+        let keyword = self.previous();
+        let mut names = Vec::<Token>::new();
+        let mut alias = None;
+
+        let path = if self.match_tokens(&[TokenType::LeftBrace]) {
+            while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+                match self.consume(TokenType::Identifier, "Expect name to import.") {
+                    Ok(name) => names.push(name),
+                    Err(err) => return Err(err),
+                }
+
+                if !self.match_tokens(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(TokenType::RightBrace, "Expect '}' after imported names.")?;
+
+            if !self.match_word("from") {
+                return Err(throw_error(self.peek(), "Expect 'from' after imported names."));
+            }
+
+            self.consume(TokenType::String, "Expect module path after 'from'.")?
+        } else {
+            let path = self.consume(TokenType::String, "Expect module path after 'import'.")?;
+
+            if self.match_word("as") {
+                match self.consume(TokenType::Identifier, "Expect module name after 'as'.") {
+                    Ok(name) => alias = Some(name),
+                    Err(err) => return Err(err),
+                }
+            }
+
+            path
         };
 
+        match self.consume(TokenType::Semicolon, "Expect ';' after import.") {
+            Ok(_) => Ok(Statement::ImportStatement(ImportStatement::new(
+                keyword, path, alias, names,
+            ))),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn if_statement(&mut self) -> Result<Statement, ParserError> {
+        let condition = self.condition("if")?;
+
+        let then_branch = self.declaration()?;
+
+        let mut else_branch = None;
+        if self.match_tokens(&[TokenType::Else]) {
+            else_branch = match self.declaration() {
+                Ok(statement) => Some(statement),
+                Err(err) => return Err(err),
+            };
+        }
+
+        Ok(Statement::IfStatement(IfStatement::new(
+            condition,
+            then_branch,
+            else_branch,
+        )))
+    }
+
+    fn while_statement(&mut self) -> Result<Statement, ParserError> {
+        let condition = self.condition("while")?;
+
+        let body = self.declaration()?;
+
+        Ok(Statement::WhileStatement(WhileStatement::new(condition, body)))
+    }
+
+    /// The parenthesised condition of an `if` or `while`.
+    fn condition(&mut self, keyword: &str) -> Result<Expr, ParserError> {
+        self.consume(
+            TokenType::LeftParen,
+            &format!("Expect '(' after '{}'.", keyword),
+        )?;
+
+        let condition = self.expression()?;
+
+        match self.consume(TokenType::RightParen, "Expect ')' after condition.") {
+            Ok(_) => Ok(condition),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn print_statement(&mut self) -> Result<Statement, ParserError> {
+        let value = self.expression()?;
+
         if self.match_tokens(&[TokenType::Semicolon]) {
             return Ok(Statement::PrintStatement(PrintStatement::new(value)));
         }
 
-        return Err(throw_error(self.peek(), "Expect ';' after value."));
+        Err(throw_error(self.peek(), "Expect ';' after value."))
     }
 
     fn expr_statement(&mut self) -> Result<Statement, ParserError> {
-        let expr = match self.expression() {
-            Ok(expr) => expr,
-            Err(err) => return Err(err),
-        };
+        let expr = self.expression()?;
 
         if self.match_tokens(&[TokenType::Semicolon]) {
             return Ok(Statement::ExpressionStatement(ExpressionStatement::new(
@@ -113,11 +449,63 @@ impl Parser {
             )));
         }
 
-        return Err(throw_error(self.peek(), "Expect ';' after expression."));
+        Err(throw_error(self.peek(), "Expect ';' after expression."))
     }
 
     fn expression(&mut self) -> Result<Expr, ParserError> {
-        return self.equality();
+        self.assignment()
+    }
+
+    fn assignment(&mut self) -> Result<Expr, ParserError> {
+        let expr = self.or()?;
+
+        if self.match_tokens(&[TokenType::Equal]) {
+            let equals = self.previous();
+            let value = self.assignment()?;
+
+            return match expr {
+                Expr::VarDeclaration(name) => Ok(AssignExpr::new(name, value).into()),
+                Expr::IndexExpr(index) => {
+                    let index = *index;
+                    Ok(SetIndexExpr::new(
+                        index.get_object().clone(),
+                        index.get_bracket().clone(),
+                        index.get_index().clone(),
+                        value,
+                    )
+                    .into())
+                }
+                _ => Err(throw_error(equals, "Invalid assignment target.")),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> Result<Expr, ParserError> {
+        let mut expr = self.and()?;
+
+        while self.match_tokens(&[TokenType::Or]) {
+            let operator = self.previous();
+            let right = self.and()?;
+
+            expr = LogicalExpr::new(expr, operator, right).into();
+        }
+
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, ParserError> {
+        let mut expr = self.equality()?;
+
+        while self.match_tokens(&[TokenType::And]) {
+            let operator = self.previous();
+            let right = self.equality()?;
+
+            expr = LogicalExpr::new(expr, operator, right).into();
+        }
+
+        Ok(expr)
     }
 
     fn equality(&mut self) -> Result<Expr, ParserError> {
@@ -125,17 +513,14 @@ impl Parser {
 
         while self.match_tokens(&[TokenType::BangEqual, TokenType::EqualEqual]) {
             let operator = self.previous();
-            let right = match self.comparison() {
-                Ok(expr) => expr,
-                Err(err) => return Err(err),
-            };
+            let right = self.comparison()?;
             expr = match expr {
                 Ok(expr) => Ok(BinaryExpr::new(expr, operator, right).into()),
                 Err(err) => return Err(err),
             }
         }
 
-        return expr;
+        expr
     }
 
     fn comparison(&mut self) -> Result<Expr, ParserError> {
@@ -148,17 +533,14 @@ impl Parser {
             TokenType::LessEqual,
         ]) {
             let operator = self.previous();
-            let right = match self.term() {
-                Ok(expr) => expr,
-                Err(err) => return Err(err),
-            };
+            let right = self.term()?;
             expr = match expr {
                 Ok(expr) => Ok(BinaryExpr::new(expr, operator, right).into()),
                 Err(err) => return Err(err),
             };
         }
 
-        return expr;
+        expr
     }
 
     fn term(&mut self) -> Result<Expr, ParserError> {
@@ -166,17 +548,14 @@ impl Parser {
 
         while self.match_tokens(&[TokenType::Minus, TokenType::Plus]) {
             let operator = self.previous();
-            let right = match self.factor() {
-                Ok(expr) => expr,
-                Err(err) => return Err(err),
-            };
+            let right = self.factor()?;
             expr = match expr {
                 Ok(expr) => Ok(BinaryExpr::new(expr, operator, right).into()),
                 Err(err) => return Err(err),
             };
         }
 
-        return expr;
+        expr
     }
 
     fn factor(&mut self) -> Result<Expr, ParserError> {
@@ -184,10 +563,7 @@ impl Parser {
 
         while self.match_tokens(&[TokenType::Slash, TokenType::Star]) {
             let operator = self.previous();
-            let right = match self.unary() {
-                Ok(expr) => expr,
-                Err(err) => return Err(err),
-            };
+            let right = self.unary()?;
 
             expr = match expr {
                 Ok(expr) => Ok(BinaryExpr::new(expr, operator, right).into()),
@@ -195,21 +571,94 @@ impl Parser {
             };
         }
 
-        return expr;
+        expr
     }
 
     fn unary(&mut self) -> Result<Expr, ParserError> {
         if self.match_tokens(&[TokenType::Bang, TokenType::Minus]) {
             let operator = self.previous();
 
-            let right = match self.unary() {
-                Ok(expr) => expr,
-                Err(err) => return Err(err),
-            };
+            let right = self.unary()?;
             return Ok(UnaryExpr::new(operator, right).into());
         }
 
-        return self.primary();
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, ParserError> {
+        let mut expr = self.primary()?;
+
+        loop {
+            if self.match_tokens(&[TokenType::LeftParen]) {
+                expr = self.finish_call(expr)?;
+            } else if self.match_tokens(&[TokenType::LeftBracket]) {
+                expr = self.finish_index(expr)?;
+            } else if self.match_tokens(&[TokenType::Dot]) {
+                let name = self.consume(TokenType::Identifier, "Expect property name after '.'.")?;
+
+                expr = GetExpr::new(expr, name).into();
+            } else {
+                break;
+            }
+        }
+
+        Ok(expr)
+    }
+
+    fn finish_index(&mut self, object: Expr) -> Result<Expr, ParserError> {
+        let bracket = self.previous();
+
+        let mut start = None;
+
+        if !self.check(TokenType::Colon) {
+            let index = self.expression()?;
+
+            if !self.check(TokenType::Colon) {
+                return match self.consume(TokenType::RightBracket, "Expect ']' after index.") {
+                    Ok(_) => Ok(IndexExpr::new(object, bracket, index).into()),
+                    Err(err) => Err(err),
+                };
+            }
+
+            start = Some(index);
+        }
+
+        // Skip the `:` separating the bounds.
+        self.advance();
+
+        let end = if self.check(TokenType::RightBracket) {
+            None
+        } else {
+            match self.expression() {
+                Ok(expr) => Some(expr),
+                Err(err) => return Err(err),
+            }
+        };
+
+        match self.consume(TokenType::RightBracket, "Expect ']' after slice.") {
+            Ok(_) => Ok(SliceExpr::new(object, bracket, start, end).into()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> Result<Expr, ParserError> {
+        let mut arguments = Vec::<Expr>::new();
+
+        if !self.check(TokenType::RightParen) {
+            loop {
+                let argument = self.expression()?;
+                arguments.push(argument);
+
+                if !self.match_tokens(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        match self.consume(TokenType::RightParen, "Expect ')' after arguments.") {
+            Ok(paren) => Ok(CallExpr::new(callee, paren, arguments).into()),
+            Err(err) => Err(err),
+        }
     }
 
     fn primary(&mut self) -> Result<Expr, ParserError> {
@@ -225,26 +674,85 @@ impl Parser {
             return Ok(Expr::Literal(Literal::Nil));
         }
 
-        if self.match_tokens(&[TokenType::Integer, TokenType::Float, TokenType::String]) {
+        if self.match_tokens(&[TokenType::Integer, TokenType::Float]) {
             return Ok(Expr::Literal(Literal::new(self.previous().lexeme.as_str())));
         }
 
+        if self.match_tokens(&[TokenType::String]) {
+            return Ok(Expr::Literal(Literal::String(self.previous().literal)));
+        }
+
         if self.match_tokens(&[TokenType::Identifier]) {
             return Ok(Expr::VarDeclaration(self.previous()));
         }
 
         if self.match_tokens(&[TokenType::LeftParen]) {
-            let expr = match self.expression() {
-                Ok(expr) => expr,
-                Err(err) => return Err(err),
-            };
-            self.consume(TokenType::RightParen, "Expected ')' after expression.");
+            let expr = self.expression()?;
+            self.consume(TokenType::RightParen, "Expected ')' after expression.")?;
             return Ok(Expr::GroupingExpr(Box::new(GroupingExpr::new(expr))));
         }
 
+        if self.match_tokens(&[TokenType::LeftBrace]) {
+            return self.map_literal();
+        }
+
+        if self.match_tokens(&[TokenType::LeftBracket]) {
+            return self.list_literal();
+        }
+
         Err(throw_error(self.peek(), "Expected expression"))
     }
 
+    fn list_literal(&mut self) -> Result<Expr, ParserError> {
+        let bracket = self.previous();
+        let mut elements = Vec::<Expr>::new();
+
+        while !self.check(TokenType::RightBracket) && !self.is_at_end() {
+            let element = self.expression()?;
+
+            elements.push(element);
+
+            if !self.match_tokens(&[TokenType::Comma]) {
+                break;
+            }
+        }
+
+        match self.consume(TokenType::RightBracket, "Expect ']' after list elements.") {
+            Ok(_) => Ok(ListExpr::new(bracket, elements).into()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn map_literal(&mut self) -> Result<Expr, ParserError> {
+        let brace = self.previous();
+        let mut entries = Vec::<(Expr, Expr)>::new();
+
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            // Bare identifiers used as keys are shorthand for string keys,
+            // so `{ b: 2 }` is the same as `{ "b": 2 }`.
+            let key = if self.check(TokenType::Identifier) && self.check_next(TokenType::Colon) {
+                Expr::Literal(Literal::String(self.advance().lexeme))
+            } else {
+                self.expression()?
+            };
+
+            self.consume(TokenType::Colon, "Expect ':' after map key.")?;
+
+            let value = self.expression()?;
+
+            entries.push((key, value));
+
+            if !self.match_tokens(&[TokenType::Comma]) {
+                break;
+            }
+        }
+
+        match self.consume(TokenType::RightBrace, "Expect '}' after map entries.") {
+            Ok(_) => Ok(MapExpr::new(brace, entries).into()),
+            Err(err) => Err(err),
+        }
+    }
+
     fn consume(&mut self, token: TokenType, message: &str) -> Result<Token, ParserError> {
         match self.check(token) {
             true => Ok(self.advance()),
@@ -260,7 +768,7 @@ impl Parser {
             }
         }
 
-        return false;
+        false
     }
 
     fn check(&mut self, token: TokenType) -> bool {
@@ -268,27 +776,45 @@ impl Parser {
             return false;
         }
 
-        return self.peek().token_type == token;
+        self.peek().token_type == token
+    }
+
+    /// Matches a contextual keyword such as `as` or `from`, which are
+    /// ordinary identifiers everywhere else.
+    fn match_word(&mut self, word: &str) -> bool {
+        if self.check(TokenType::Identifier) && self.peek().lexeme == word {
+            self.advance();
+            return true;
+        }
+
+        false
+    }
+
+    fn check_next(&mut self, token: TokenType) -> bool {
+        match self.tokens.get(self.current as usize + 1) {
+            Some(next) => next.token_type == token,
+            None => false,
+        }
     }
 
     fn advance(&mut self) -> Token {
         if !self.is_at_end() {
             self.current += 1
         };
-        return self.previous();
+        self.previous()
     }
 
     fn is_at_end(&mut self) -> bool {
-        return self.peek().token_type == TokenType::Eof;
+        self.peek().token_type == TokenType::Eof
     }
 
     fn peek(&mut self) -> Token {
-        return self.tokens.get(self.current as usize).unwrap().clone();
+        self.tokens.get(self.current as usize).unwrap().clone()
     }
 
     fn previous(&mut self) -> Token {
-        let current = self.current.clone() as usize;
-        return self.tokens.get(current - 1).unwrap().clone();
+        let current = self.current as usize;
+        self.tokens.get(current - 1).unwrap().clone()
     }
 
     fn synchronize(&mut self) {
@@ -307,6 +833,10 @@ impl Parser {
                 | TokenType::If
                 | TokenType::While
                 | TokenType::Print
+                | TokenType::Import
+                | TokenType::Export
+                | TokenType::Throw
+                | TokenType::Try
                 | TokenType::Return => return,
                 _ => self.advance(),
             };
@@ -1,44 +1,81 @@
-use std::os::windows::process;
-
 use crate::{
-    error::interpreter::throw_runtime_error, lang::taco::Taco, parser::parser::Parser,
+    error::{handling::error, interpreter::throw_runtime_error, parser::report_parser_error},
+    interpreter::interpreter::Value,
+    lang::taco::Taco,
+    parser::parser::Parser,
+    syntax::statement::Statement,
     token::scanner::Scanner,
 };
 
 pub fn exec(source: String, lang: &mut Taco) {
-    let mut scanner = Scanner::new(source);
-    let tokens = scanner.scan_tokens();
-    let mut parser = Parser::new(tokens.clone());
-    let expr = parser.parse();
-
-    match expr {
-        Ok(_) => (),
-        Err(_e) => {
-            lang.set_error(true);
-            // std::process::exit(65);
-            return;
-        }
-    }
+    let expr = match parse(source, lang) {
+        Some(statements) => Ok(statements),
+        None => return,
+    };
 
-    let err = lang.interpreter().interpret(expr.clone());
+    let err = lang.interpreter().interpret(expr);
 
     match err {
         Ok(_) => {
             if lang.has_error {
                 // std::process::exit(65);
-                return;
-            };
-
-            ()
+            }
         }
         Err(e) => {
-            throw_runtime_error(e);
+            throw_runtime_error(&lang.output(), e);
             lang.set_runtime_error(true);
             // std::process::exit(70);
-            return;
         }
     }
 
     // println!("{:?}\n\n", tokens.clone());
     // println!("{:?}\n\n", expr.clone().unwrap());
 }
+
+/// Runs a line of REPL input like `exec`, then shows the value of a final
+/// expression statement, unless it is nil, on the interpreter's stdout.
+pub fn exec_repl(source: String, lang: &mut Taco) {
+    let statements = match parse(source, lang) {
+        Some(statements) => statements,
+        None => return,
+    };
+
+    let interpreter = lang.interpreter();
+    match interpreter.interpret_value(statements) {
+        Ok(Value::Nil) => (),
+        Ok(value) => {
+            let echo = format!("=> {}", interpreter.repr(value));
+            interpreter.get_output().println(&echo);
+        }
+        Err(e) => {
+            throw_runtime_error(interpreter.get_output(), e);
+            lang.set_runtime_error(true);
+        }
+    }
+}
+
+/// Scans and parses `source`, reporting any errors and setting the error
+/// flag if there were some.
+pub fn parse(source: String, lang: &mut Taco) -> Option<Vec<Statement>> {
+    let output = lang.output();
+    let mut scanner = Scanner::new(source);
+    let tokens = scanner.scan_tokens();
+    if !scanner.errors.is_empty() {
+        for (line, message) in &scanner.errors {
+            error(&output, *line, message);
+        }
+        lang.set_error(true);
+        return None;
+    }
+
+    let mut parser = Parser::new(tokens);
+    match parser.parse() {
+        Ok(statements) => Some(statements),
+        Err(e) => {
+            report_parser_error(&output, &e);
+            lang.set_error(true);
+            // std::process::exit(65);
+            None
+        }
+    }
+}
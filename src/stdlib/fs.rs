@@ -0,0 +1,97 @@
+use std::{fs, io::Write, path::Path};
+
+use crate::{
+    interpreter::{
+        interpreter::{Interpreter, Value},
+        native::Arity,
+    },
+    stdlib::Module,
+};
+
+/// Defines the `fs` namespace. OS failures such as missing files or denied
+/// permissions surface as runtime errors naming the path involved.
+pub fn register(interpreter: &mut Interpreter) {
+    let mut module = Module::new("fs");
+
+    module.function("read_file", Arity::Exact(1), read_file);
+    module.function("write_file", Arity::Exact(2), write_file);
+    module.function("append_file", Arity::Exact(2), append_file);
+    module.function("exists", Arity::Exact(1), exists);
+    module.function("list_dir", Arity::Exact(1), list_dir);
+
+    module.register(interpreter);
+}
+
+fn string_argument(function: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(string) => Ok(string.clone()),
+        other => Err(format!(
+            "fs.{}() expects a string but got {}.",
+            function,
+            other.type_name()
+        )),
+    }
+}
+
+fn read_file(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let path = string_argument("read_file", &args[0])?;
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(Value::String(contents)),
+        Err(err) => Err(format!("Could not read '{}': {}", path, err)),
+    }
+}
+
+fn write_file(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let path = string_argument("write_file", &args[0])?;
+    let contents = string_argument("write_file", &args[1])?;
+
+    match fs::write(&path, contents) {
+        Ok(_) => Ok(Value::Nil),
+        Err(err) => Err(format!("Could not write '{}': {}", path, err)),
+    }
+}
+
+fn append_file(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let path = string_argument("append_file", &args[0])?;
+    let contents = string_argument("append_file", &args[1])?;
+
+    let result = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()));
+
+    match result {
+        Ok(_) => Ok(Value::Nil),
+        Err(err) => Err(format!("Could not append to '{}': {}", path, err)),
+    }
+}
+
+fn exists(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let path = string_argument("exists", &args[0])?;
+    Ok(Value::Boolean(Path::new(&path).exists()))
+}
+
+/// Lists the entry names in a directory, sorted so scripts see the same
+/// order on every platform.
+fn list_dir(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let path = string_argument("list_dir", &args[0])?;
+
+    let entries = match fs::read_dir(&path) {
+        Ok(entries) => entries,
+        Err(err) => return Err(format!("Could not list '{}': {}", path, err)),
+    };
+
+    let mut names = Vec::<String>::new();
+    for entry in entries {
+        match entry {
+            Ok(entry) => names.push(entry.file_name().to_string_lossy().into_owned()),
+            Err(err) => return Err(format!("Could not list '{}': {}", path, err)),
+        }
+    }
+    names.sort();
+
+    let names = names.into_iter().map(Value::String).collect();
+    Ok(Value::list(names))
+}
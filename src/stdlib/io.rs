@@ -0,0 +1,45 @@
+use std::io::{self, BufRead};
+
+use crate::{
+    interpreter::{
+        interpreter::{Interpreter, Value},
+        native::Arity,
+    },
+    stdlib::Module,
+};
+
+/// Defines the `io` namespace for the standard streams.
+pub fn register(interpreter: &mut Interpreter) {
+    let mut module = Module::new("io");
+
+    module.function("read_line", Arity::Exact(0), |_, _| read_line());
+    module.function("eprint", Arity::Exact(1), eprint);
+
+    module.register(interpreter);
+}
+
+/// Reads one line from stdin without its trailing newline. Returns nil at
+/// end of input.
+pub fn read_line() -> Result<Value, String> {
+    let mut line = String::new();
+
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) => Ok(Value::Nil),
+        Ok(_) => {
+            let trimmed = line.trim_end_matches(['\n', '\r']).len();
+            line.truncate(trimmed);
+            Ok(Value::String(line))
+        }
+        Err(err) => Err(format!("Could not read from stdin: {}", err)),
+    }
+}
+
+fn eprint(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let text = interpreter.stringify(args[0].clone());
+    interpreter.charge_output(text.len() + 1)?;
+
+    match interpreter.get_output().write_stderr(&format!("{}\n", text)) {
+        Ok(_) => Ok(Value::Nil),
+        Err(err) => Err(format!("Could not write to stderr: {}", err)),
+    }
+}
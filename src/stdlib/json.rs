@@ -0,0 +1,54 @@
+use crate::{
+    interpreter::{
+        interpreter::{Interpreter, Value},
+        native::Arity,
+    },
+    stdlib::Module,
+};
+
+/// Defines the `json` namespace. Arrays become lists, objects become maps
+/// and `null` becomes nil, and the reverse when stringifying.
+pub fn register(interpreter: &mut Interpreter) {
+    let mut module = Module::new("json");
+
+    module.function("parse", Arity::Exact(1), parse);
+    module.function("stringify", Arity::Range(1, 2), stringify);
+
+    module.register(interpreter);
+}
+
+fn parse(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let text = match &args[0] {
+        Value::String(text) => text,
+        other => {
+            return Err(format!(
+                "json.parse() expects a string but got {}.",
+                other.type_name()
+            ))
+        }
+    };
+
+    serde_json::from_str(text).map_err(|err| format!("Invalid JSON: {}.", err))
+}
+
+/// Writes compact JSON, or indented JSON when the second argument is true.
+fn stringify(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let pretty = match args.get(1) {
+        None => false,
+        Some(Value::Boolean(pretty)) => *pretty,
+        Some(other) => {
+            return Err(format!(
+                "json.stringify() expects a boolean for 'pretty' but got {}.",
+                other.type_name()
+            ))
+        }
+    };
+
+    let text = match pretty {
+        true => serde_json::to_string_pretty(&args[0]),
+        false => serde_json::to_string(&args[0]),
+    };
+
+    text.map(Value::String)
+        .map_err(|err| format!("Cannot convert to JSON: {}.", err))
+}
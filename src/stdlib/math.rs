@@ -0,0 +1,189 @@
+use std::f64::consts::{E, PI};
+
+use crate::{
+    interpreter::{
+        interpreter::{Interpreter, Value},
+        native::Arity,
+    },
+    stdlib::Module,
+};
+
+/// Defines the `math` namespace. Functions accept integers and floats
+/// alike; arguments outside a function's domain are runtime errors rather
+/// than `NaN`s.
+pub fn register(interpreter: &mut Interpreter) {
+    let mut math = Module::new("math");
+
+    math.constant("pi", Value::Float(PI));
+    math.constant("e", Value::Float(E));
+
+    math.function("sqrt", Arity::Exact(1), sqrt);
+    math.function("pow", Arity::Exact(2), pow);
+    math.function("abs", Arity::Exact(1), abs);
+    math.function("floor", Arity::Exact(1), |_, args| round_with("floor", &args[0], f64::floor));
+    math.function("ceil", Arity::Exact(1), |_, args| round_with("ceil", &args[0], f64::ceil));
+    math.function("round", Arity::Exact(1), |_, args| round_with("round", &args[0], f64::round));
+    math.function("min", Arity::AtLeast(1), |_, args| extreme("min", args, |a, b| b < a));
+    math.function("max", Arity::AtLeast(1), |_, args| extreme("max", args, |a, b| b > a));
+    math.function("sin", Arity::Exact(1), |_, args| trig("sin", &args[0], f64::sin));
+    math.function("cos", Arity::Exact(1), |_, args| trig("cos", &args[0], f64::cos));
+    math.function("tan", Arity::Exact(1), |_, args| trig("tan", &args[0], f64::tan));
+    math.function("asin", Arity::Exact(1), |_, args| inverse_trig("asin", &args[0], f64::asin));
+    math.function("acos", Arity::Exact(1), |_, args| inverse_trig("acos", &args[0], f64::acos));
+    math.function("atan", Arity::Exact(1), |_, args| Ok(Value::Float(number("atan", &args[0])?.atan())));
+    math.function("atan2", Arity::Exact(2), |_, args| {
+        let y = number("atan2", &args[0])?;
+        let x = number("atan2", &args[1])?;
+        Ok(Value::Float(y.atan2(x)))
+    });
+    math.function("gcd", Arity::Exact(2), gcd);
+
+    math.register(interpreter);
+}
+
+fn number(function: &str, value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Integer(integer) => Ok(*integer as f64),
+        Value::Float(float) => Ok(*float),
+        other => Err(format!(
+            "math.{}() expects a number but got {}.",
+            function,
+            other.type_name()
+        )),
+    }
+}
+
+fn integer(function: &str, value: &Value) -> Result<i64, String> {
+    match value {
+        Value::Integer(integer) => Ok(*integer),
+        other => Err(format!(
+            "math.{}() expects an integer but got {}.",
+            function,
+            other.type_name()
+        )),
+    }
+}
+
+fn domain_error(function: &str, reason: &str) -> String {
+    format!("math.{}() domain error: {}.", function, reason)
+}
+
+fn sqrt(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let value = number("sqrt", &args[0])?;
+
+    if value < 0.0 {
+        return Err(domain_error("sqrt", "argument must not be negative"));
+    }
+
+    Ok(Value::Float(value.sqrt()))
+}
+
+/// Integer powers with a non-negative exponent stay integers; everything
+/// else is computed in floating point.
+fn pow(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    if let (Value::Integer(base), Value::Integer(exponent)) = (&args[0], &args[1]) {
+        if *exponent >= 0 {
+            let result = u32::try_from(*exponent)
+                .ok()
+                .and_then(|exponent| base.checked_pow(exponent));
+
+            return match result {
+                Some(result) => Ok(Value::Integer(result)),
+                None => Err("math.pow() overflowed.".to_string()),
+            };
+        }
+    }
+
+    let base = number("pow", &args[0])?;
+    let exponent = number("pow", &args[1])?;
+    let result = base.powf(exponent);
+
+    if base == 0.0 && exponent < 0.0 {
+        return Err(domain_error("pow", "zero cannot be raised to a negative power"));
+    }
+    if result.is_nan() {
+        return Err(domain_error("pow", "result is not a real number"));
+    }
+    // Infinite arguments may give infinite results; finite ones must not.
+    if result.is_infinite() && base.is_finite() && exponent.is_finite() {
+        return Err(domain_error("pow", "result is too large to represent"));
+    }
+
+    Ok(Value::Float(result))
+}
+
+fn abs(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    match &args[0] {
+        Value::Integer(integer) => match integer.checked_abs() {
+            Some(result) => Ok(Value::Integer(result)),
+            None => Err("math.abs() overflowed.".to_string()),
+        },
+        other => Ok(Value::Float(number("abs", other)?.abs())),
+    }
+}
+
+/// Rounds towards an integer, returning an integer value.
+fn round_with(function: &str, value: &Value, round: fn(f64) -> f64) -> Result<Value, String> {
+    if let Value::Integer(integer) = value {
+        return Ok(Value::Integer(*integer));
+    }
+
+    let rounded = round(number(function, value)?);
+
+    if !rounded.is_finite() || rounded < i64::MIN as f64 || rounded >= i64::MAX as f64 {
+        return Err(domain_error(function, "result does not fit in an integer"));
+    }
+
+    Ok(Value::Integer(rounded as i64))
+}
+
+/// Returns whichever argument wins `replaces`, keeping its original type.
+fn extreme(function: &str, args: Vec<Value>, replaces: fn(f64, f64) -> bool) -> Result<Value, String> {
+    let mut best = args[0].clone();
+    let mut best_number = number(function, &best)?;
+
+    for arg in args.into_iter().skip(1) {
+        let current = number(function, &arg)?;
+        if replaces(best_number, current) {
+            best = arg;
+            best_number = current;
+        }
+    }
+
+    Ok(best)
+}
+
+/// An angle has to be finite; the sine of infinity is not a number.
+fn trig(function: &str, value: &Value, op: fn(f64) -> f64) -> Result<Value, String> {
+    let value = number(function, value)?;
+
+    if !value.is_finite() {
+        return Err(domain_error(function, "argument must be finite"));
+    }
+
+    Ok(Value::Float(op(value)))
+}
+
+fn inverse_trig(function: &str, value: &Value, op: fn(f64) -> f64) -> Result<Value, String> {
+    let value = number(function, value)?;
+
+    if !(-1.0..=1.0).contains(&value) {
+        return Err(domain_error(function, "argument must be between -1 and 1"));
+    }
+
+    Ok(Value::Float(op(value)))
+}
+
+fn gcd(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let mut a = integer("gcd", &args[0])?.unsigned_abs();
+    let mut b = integer("gcd", &args[1])?.unsigned_abs();
+
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    match i64::try_from(a) {
+        Ok(result) => Ok(Value::Integer(result)),
+        Err(_) => Err("math.gcd() overflowed.".to_string()),
+    }
+}
@@ -0,0 +1,47 @@
+pub mod prelude;
+pub mod string;
+pub mod math;
+pub mod io;
+pub mod fs;
+#[cfg(feature = "serde")]
+pub mod json;
+
+use crate::interpreter::{
+    interpreter::{Interpreter, Value},
+    map::{HashKey, ValueMap},
+    native::{Arity, NativeFunction},
+};
+
+/// A namespace of natives and constants, exposed to scripts as a map so that
+/// `module.member` reads a member.
+pub struct Module {
+    name: String,
+    members: ValueMap,
+}
+
+impl Module {
+    pub fn new(name: &str) -> Module {
+        Module {
+            name: name.to_string(),
+            members: ValueMap::new(),
+        }
+    }
+
+    pub fn function<F>(&mut self, name: &str, arity: Arity, function: F)
+    where
+        F: Fn(&mut Interpreter, Vec<Value>) -> Result<Value, String> + 'static,
+    {
+        let qualified = format!("{}.{}", self.name, name);
+        let native = NativeFunction::new(&qualified, arity, function);
+        self.constant(name, Value::NativeFunction(native));
+    }
+
+    pub fn constant(&mut self, name: &str, value: Value) {
+        self.members.insert(HashKey::String(name.to_string()), value);
+    }
+
+    /// Defines the module as a global named after it.
+    pub fn register(self, interpreter: &mut Interpreter) {
+        interpreter.define_global(&self.name, Value::map(self.members));
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    interpreter::{
+        interpreter::{Interpreter, Value},
+        native::Arity,
+    },
+    stdlib,
+};
+
+/// Defines the built-in functions every script can call without importing
+/// anything.
+pub fn register(interpreter: &mut Interpreter) {
+    interpreter.define_native("clock", Arity::Exact(0), clock);
+    interpreter.define_native("len", Arity::Exact(1), len);
+    interpreter.define_native("type", Arity::Exact(1), type_of);
+    interpreter.define_native("str", Arity::Exact(1), str);
+    interpreter.define_native("int", Arity::Exact(1), int);
+    interpreter.define_native("float", Arity::Exact(1), float);
+    interpreter.define_native("input", Arity::Range(0, 1), input);
+    interpreter.define_native("assert", Arity::Range(1, 2), assert);
+}
+
+/// Seconds since the unix epoch, as a float.
+fn clock(_: &mut Interpreter, _: Vec<Value>) -> Result<Value, String> {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => Ok(Value::Float(duration.as_secs_f64())),
+        Err(err) => Err(format!("System clock is before the unix epoch: {}", err)),
+    }
+}
+
+fn len(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    match &args[0] {
+        Value::String(string) => Ok(Value::Integer(string.chars().count() as i64)),
+        Value::List(list) => Ok(Value::Integer(list.borrow().len() as i64)),
+        Value::Map(map) => Ok(Value::Integer(map.borrow().len() as i64)),
+        other => Err(format!("Cannot take the length of {}.", other.type_name())),
+    }
+}
+
+fn type_of(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    Ok(Value::String(args[0].type_name().to_string()))
+}
+
+fn str(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    let value = args.into_iter().next().unwrap_or(Value::Nil);
+    Ok(Value::String(interpreter.stringify(value)))
+}
+
+fn int(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    match &args[0] {
+        Value::Integer(integer) => Ok(Value::Integer(*integer)),
+        Value::Float(float) => {
+            if !float.is_finite() {
+                return Err(format!("Cannot convert {} to integer.", float));
+            }
+
+            // `as` would saturate, so check the range first. 2^63 itself is
+            // already too big, which is why the upper bound is exclusive.
+            let truncated = float.trunc();
+            if truncated < i64::MIN as f64 || truncated >= i64::MAX as f64 {
+                return Err(format!("Cannot convert {}: it does not fit in an integer.", float));
+            }
+
+            Ok(Value::Integer(truncated as i64))
+        }
+        Value::Boolean(boolean) => Ok(Value::Integer(*boolean as i64)),
+        Value::String(string) => match string.trim().parse::<i64>() {
+            Ok(integer) => Ok(Value::Integer(integer)),
+            Err(_) => Err(format!("Cannot convert {:?} to integer.", string)),
+        },
+        other => Err(format!("Cannot convert {} to integer.", other.type_name())),
+    }
+}
+
+fn float(_: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    match &args[0] {
+        Value::Integer(integer) => Ok(Value::Float(*integer as f64)),
+        Value::Float(float) => Ok(Value::Float(*float)),
+        Value::String(string) => match string.trim().parse::<f64>() {
+            Ok(float) => Ok(Value::Float(float)),
+            Err(_) => Err(format!("Cannot convert {:?} to float.", string)),
+        },
+        other => Err(format!("Cannot convert {} to float.", other.type_name())),
+    }
+}
+
+/// Reads one line from stdin without its trailing newline, optionally
+/// printing a prompt first. Returns nil at end of input.
+fn input(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    if let Some(prompt) = args.into_iter().next() {
+        let prompt = interpreter.stringify(prompt);
+        interpreter.charge_output(prompt.len())?;
+        let output = interpreter.get_output();
+        if let Err(err) = output.write_stdout(&prompt).and_then(|_| output.flush()) {
+            return Err(err.to_string());
+        }
+    }
+
+    stdlib::io::read_line()
+}
+
+fn assert(interpreter: &mut Interpreter, args: Vec<Value>) -> Result<Value, String> {
+    if args[0].is_truthy() {
+        return Ok(Value::Nil);
+    }
+
+    match args.get(1) {
+        Some(message) => Err(format!(
+            "Assertion failed: {}",
+            interpreter.stringify(message.clone())
+        )),
+        None => Err("Assertion failed".to_string()),
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    interpreter::{
+        interpreter::Value,
+        native::{Arity, NativeFunction},
+    },
+    util::{normalize_index, slice_bounds},
+};
+
+/// Looks up a method on a string value, returning it bound to `receiver`.
+///
+/// Every operation works on `char`s rather than bytes, so lengths, indices
+/// and slices count unicode scalar values.
+pub fn method(receiver: &str, name: &str) -> Option<NativeFunction> {
+    let this = receiver.to_string();
+
+    let method = match name {
+        "len" => NativeFunction::new("len", Arity::Exact(0), move |_, _| {
+            Ok(Value::Integer(this.chars().count() as i64))
+        }),
+        "upper" => NativeFunction::new("upper", Arity::Exact(0), move |_, _| {
+            Ok(Value::String(this.to_uppercase()))
+        }),
+        "lower" => NativeFunction::new("lower", Arity::Exact(0), move |_, _| {
+            Ok(Value::String(this.to_lowercase()))
+        }),
+        "trim" => NativeFunction::new("trim", Arity::Exact(0), move |_, _| {
+            Ok(Value::String(this.trim().to_string()))
+        }),
+        "split" => NativeFunction::new("split", Arity::Range(0, 1), move |_, args| {
+            let parts: Vec<Value> = match args.first() {
+                None => this
+                    .split_whitespace()
+                    .map(|part| Value::String(part.to_string()))
+                    .collect(),
+                Some(separator) => {
+                    let separator = string_argument("split", separator)?;
+                    if separator.is_empty() {
+                        this.chars().map(|c| Value::String(c.to_string())).collect()
+                    } else {
+                        this.split(separator.as_str())
+                            .map(|part| Value::String(part.to_string()))
+                            .collect()
+                    }
+                }
+            };
+
+            Ok(Value::list(parts))
+        }),
+        "contains" => NativeFunction::new("contains", Arity::Exact(1), move |_, args| {
+            let needle = string_argument("contains", &args[0])?;
+            Ok(Value::Boolean(this.contains(needle.as_str())))
+        }),
+        "replace" => NativeFunction::new("replace", Arity::Exact(2), move |_, args| {
+            let from = string_argument("replace", &args[0])?;
+            let to = string_argument("replace", &args[1])?;
+            Ok(Value::String(this.replace(from.as_str(), to.as_str())))
+        }),
+        "starts_with" => NativeFunction::new("starts_with", Arity::Exact(1), move |_, args| {
+            let prefix = string_argument("starts_with", &args[0])?;
+            Ok(Value::Boolean(this.starts_with(prefix.as_str())))
+        }),
+        _ => return None,
+    };
+
+    Some(method)
+}
+
+/// Returns the character at `index` as a one-character string.
+pub fn char_at(string: &str, index: i64) -> Option<String> {
+    let len = string.chars().count();
+    normalize_index(index, len).and_then(|index| string.chars().nth(index).map(String::from))
+}
+
+pub fn slice(string: &str, start: Option<i64>, end: Option<i64>) -> String {
+    let (start, end) = slice_bounds(string.chars().count(), start, end);
+    string.chars().skip(start).take(end - start).collect()
+}
+
+fn string_argument(method: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(string) => Ok(string.clone()),
+        other => Err(format!(
+            "{}() expects a string argument but got {}.",
+            method,
+            other.type_name()
+        )),
+    }
+}
+
@@ -0,0 +1,12 @@
+use crate::syntax::statement::Statement;
+
+#[derive(Clone, Debug)]
+pub struct BlockStatement {
+    pub statements: Vec<Statement>,
+}
+
+impl BlockStatement {
+    pub fn new(statements: Vec<Statement>) -> Self {
+        Self { statements }
+    }
+}
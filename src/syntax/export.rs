@@ -0,0 +1,18 @@
+use crate::syntax::statement::Statement;
+use crate::token::tokens::Token;
+
+/// A top-level declaration whose binding is visible to importers.
+#[derive(Clone, Debug)]
+pub struct ExportStatement {
+    pub keyword: Token,
+    pub declaration: Box<Statement>,
+}
+
+impl ExportStatement {
+    pub fn new(keyword: Token, declaration: Statement) -> Self {
+        Self {
+            keyword,
+            declaration: Box::new(declaration),
+        }
+    }
+}
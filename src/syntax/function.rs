@@ -0,0 +1,22 @@
+use std::rc::Rc;
+
+use crate::{syntax::statement::Statement, token::tokens::Token};
+
+/// `taco name(params) { body }`. The body is shared so that function values
+/// created from the declaration don't copy it.
+#[derive(Clone, Debug)]
+pub struct FunctionStatement {
+    pub name: Token,
+    pub params: Vec<Token>,
+    pub body: Rc<Vec<Statement>>,
+}
+
+impl FunctionStatement {
+    pub fn new(name: Token, params: Vec<Token>, body: Vec<Statement>) -> FunctionStatement {
+        FunctionStatement {
+            name,
+            params,
+            body: Rc::new(body),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::{core::expression::Expr, syntax::statement::Statement};
+
+#[derive(Clone, Debug)]
+pub struct IfStatement {
+    pub condition: Expr,
+    pub then_branch: Box<Statement>,
+    pub else_branch: Option<Box<Statement>>,
+}
+
+impl IfStatement {
+    pub fn new(condition: Expr, then_branch: Statement, else_branch: Option<Statement>) -> Self {
+        Self {
+            condition,
+            then_branch: Box::new(then_branch),
+            else_branch: else_branch.map(Box::new),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::token::tokens::Token;
+
+/// `import "path" as alias;` or `import { a, b } from "path";`.
+#[derive(Clone, Debug)]
+pub struct ImportStatement {
+    pub keyword: Token,
+    pub path: Token,
+    pub alias: Option<Token>,
+    pub names: Vec<Token>,
+}
+
+impl ImportStatement {
+    pub fn new(keyword: Token, path: Token, alias: Option<Token>, names: Vec<Token>) -> Self {
+        Self {
+            keyword,
+            path,
+            alias,
+            names,
+        }
+    }
+}
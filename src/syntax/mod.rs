@@ -1,4 +1,13 @@
 pub mod statement;
 pub mod expression;
 pub mod print;
-pub mod r#let;
\ No newline at end of file
+pub mod r#let;
+pub mod block;
+pub mod import;
+pub mod export;
+pub mod throw;
+pub mod r#try;
+pub mod function;
+pub mod r#return;
+pub mod r#if;
+pub mod r#while;
\ No newline at end of file
@@ -0,0 +1,13 @@
+use crate::{core::expression::Expr, token::tokens::Token};
+
+#[derive(Clone, Debug)]
+pub struct ReturnStatement {
+    pub keyword: Token,
+    pub value: Option<Expr>,
+}
+
+impl ReturnStatement {
+    pub fn new(keyword: Token, value: Option<Expr>) -> ReturnStatement {
+        ReturnStatement { keyword, value }
+    }
+}
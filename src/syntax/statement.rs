@@ -1,5 +1,14 @@
+use crate::syntax::block::BlockStatement;
+use crate::syntax::export::ExportStatement;
 use crate::syntax::expression::ExpressionStatement;
+use crate::syntax::function::FunctionStatement;
+use crate::syntax::r#if::IfStatement;
+use crate::syntax::import::ImportStatement;
 use crate::syntax::print::PrintStatement;
+use crate::syntax::r#return::ReturnStatement;
+use crate::syntax::r#try::TryStatement;
+use crate::syntax::throw::ThrowStatement;
+use crate::syntax::r#while::WhileStatement;
 
 use super::r#let::LetStatement;
 
@@ -8,10 +17,34 @@ pub enum Statement {
     ExpressionStatement(ExpressionStatement),
     PrintStatement(PrintStatement),
     LetStatement(LetStatement),
-    // BlockStatement(BlockStatement),
-    // IfStatement(IfStatement),
-    // WhileStatement(WhileStatement),
-    // FunctionStatement(FunctionStatement),
-    // ReturnStatement(ReturnStatement),
+    BlockStatement(BlockStatement),
+    ImportStatement(ImportStatement),
+    ExportStatement(ExportStatement),
+    ThrowStatement(ThrowStatement),
+    TryStatement(TryStatement),
+    IfStatement(IfStatement),
+    WhileStatement(WhileStatement),
+    FunctionStatement(FunctionStatement),
+    ReturnStatement(ReturnStatement),
     // ClassStatement(ClassStatement)
 }
+
+impl Statement {
+    /// The line the statement is on, as far as its tokens tell.
+    pub fn line(&self) -> Option<u32> {
+        match self {
+            Statement::ExpressionStatement(statement) => statement.expression.line(),
+            Statement::PrintStatement(statement) => statement.expression.line(),
+            Statement::LetStatement(declaration) => Some(declaration.name.get_line()),
+            Statement::BlockStatement(block) => block.statements.iter().find_map(Statement::line),
+            Statement::ImportStatement(import) => Some(import.keyword.get_line()),
+            Statement::ExportStatement(export) => Some(export.keyword.get_line()),
+            Statement::ThrowStatement(throw) => Some(throw.keyword.get_line()),
+            Statement::TryStatement(statement) => statement.body.iter().find_map(Statement::line),
+            Statement::IfStatement(statement) => statement.condition.line(),
+            Statement::WhileStatement(statement) => statement.condition.line(),
+            Statement::FunctionStatement(declaration) => Some(declaration.name.get_line()),
+            Statement::ReturnStatement(statement) => Some(statement.keyword.get_line()),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{core::expression::Expr, token::tokens::Token};
+
+#[derive(Clone, Debug)]
+pub struct ThrowStatement {
+    pub keyword: Token,
+    pub value: Expr,
+}
+
+impl ThrowStatement {
+    pub fn new(keyword: Token, value: Expr) -> Self {
+        Self { keyword, value }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{syntax::statement::Statement, token::tokens::Token};
+
+/// The `catch` clause of a try statement. `name` is bound to the caught
+/// value and may be left out, as in `catch { ... }`.
+#[derive(Clone, Debug)]
+pub struct CatchClause {
+    pub name: Option<Token>,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TryStatement {
+    pub body: Vec<Statement>,
+    pub catch: Option<CatchClause>,
+    pub finally: Option<Vec<Statement>>,
+}
+
+impl TryStatement {
+    pub fn new(
+        body: Vec<Statement>,
+        catch: Option<CatchClause>,
+        finally: Option<Vec<Statement>>,
+    ) -> Self {
+        Self {
+            body,
+            catch,
+            finally,
+        }
+    }
+}
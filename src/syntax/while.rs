@@ -0,0 +1,16 @@
+use crate::{core::expression::Expr, syntax::statement::Statement};
+
+#[derive(Clone, Debug)]
+pub struct WhileStatement {
+    pub condition: Expr,
+    pub body: Box<Statement>,
+}
+
+impl WhileStatement {
+    pub fn new(condition: Expr, body: Statement) -> Self {
+        Self {
+            condition,
+            body: Box::new(body),
+        }
+    }
+}
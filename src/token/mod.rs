@@ -1,2 +1,3 @@
 pub mod tokens;
-pub mod scanner;
\ No newline at end of file
+pub mod scanner;
+pub mod symbol;
\ No newline at end of file
@@ -1,30 +1,44 @@
 use std::collections::HashMap;
 
-use crate::error::handling::error;
 use crate::token::tokens::{Token, TokenType};
 
 pub struct Scanner {
     pub source: String,
     pub tokens: Vec<Token>,
+    /// Where each token is in the source, as a range of char offsets.
+    pub spans: Vec<(u32, u32)>,
     pub keywords: HashMap<String, TokenType>,
     start: u32,
     current: u32,
-    line: u8,
+    line: u32,
+    // Length in chars; `start` and `current` index chars, not bytes.
+    length: u32,
+    /// Lexical errors, by line. Scanning carries on past them.
+    pub errors: Vec<(u32, String)>,
+    /// Whether the source ended inside a backtick string or a block
+    /// comment, so more input could still complete it.
+    pub unterminated: bool,
 }
 
 impl Scanner {
     pub fn new(source: String) -> Scanner {
+        let length = source.chars().count() as u32;
+
         Scanner {
             source,
+            length,
             tokens: Vec::new(),
+            spans: Vec::new(),
             start: 0,
             current: 0,
             line: 1,
-            keywords: Scanner::create_keywords()
+            keywords: Scanner::create_keywords(),
+            errors: Vec::new(),
+            unterminated: false,
         }
     }
 
-    fn create_keywords() -> HashMap<String, TokenType> {
+    pub fn create_keywords() -> HashMap<String, TokenType> {
       let mut map = HashMap::new();
       map.insert(String::from("and"), TokenType::And);
       map.insert(String::from("class"), TokenType::Class);
@@ -42,32 +56,38 @@ impl Scanner {
       map.insert(String::from("true"), TokenType::True);
       map.insert(String::from("let"), TokenType::Let);
       map.insert(String::from("while"), TokenType::While);
-
-      return map;
+      map.insert(String::from("import"), TokenType::Import);
+      map.insert(String::from("export"), TokenType::Export);
+      map.insert(String::from("try"), TokenType::Try);
+      map.insert(String::from("catch"), TokenType::Catch);
+      map.insert(String::from("finally"), TokenType::Finally);
+      map.insert(String::from("throw"), TokenType::Throw);
+
+      map
     }
 
     fn get_keywords(&mut self) -> HashMap<String, TokenType> {
-      return self.keywords.clone();
+      self.keywords.clone()
     }
 
     fn is_at_end(&mut self) -> bool {
-        return self.current >= self.source.len() as u32;
+        self.current >= self.length
     }
 
     fn advance(&mut self) -> char {
         self.current += 1;
-        return self
+        self
             .source
             .chars()
             .nth((self.current - 1) as usize)
-            .unwrap();
+            .unwrap()
     }
 
-    fn add_token(&mut self, type_: TokenType) -> () {
+    fn add_token(&mut self, type_: TokenType) {
         self.add_token_with_literal(type_, String::new());
     }
 
-    fn add_token_with_literal(&mut self, type_: TokenType, literal: String) -> () {
+    fn add_token_with_literal(&mut self, type_: TokenType, literal: String) {
         let text = self
             .source
             .chars()
@@ -75,11 +95,16 @@ impl Scanner {
             .take((self.current - self.start) as usize)
             .collect::<String>();
         self.tokens
-            .push(Token::new(type_, text, literal, self.line.into()));
+            .push(Token::new(type_, text, literal, self.line));
+        self.spans.push((self.start, self.current));
+    }
+
+    fn error(&mut self, message: &str) {
+        self.errors.push((self.line, message.to_string()));
     }
 
     fn match_next(&mut self, token: char) -> bool {
-        return self.source.chars().nth(self.current as usize).unwrap() == token;
+        self.peek() == token
     }
 
     fn peek(&mut self) -> char {
@@ -87,28 +112,28 @@ impl Scanner {
             return '\0';
         }
 
-        return self.source.chars().nth(self.current as usize).unwrap();
+        self.source.chars().nth(self.current as usize).unwrap()
     }
 
     fn peek_next(&mut self) -> char {
-        if self.current + 1 >= self.source.len() as u32 {
+        if self.current + 1 >= self.length {
             return '\0';
         }
 
-        return self.source.chars().nth((self.current + 1) as usize).unwrap();
+        self.source.chars().nth((self.current + 1) as usize).unwrap()
     }
 
-    fn handle_string(&mut self) -> () {
+    fn handle_string(&mut self) {
         while self.peek() != '"' && !self.is_at_end() {
             if self.peek() == '\n' {
-                error(self.line, "Unterminated string");
+                self.error("Unterminated string");
                 return;
             }
             self.advance();
         }
 
         if self.is_at_end() {
-            error(self.line, "Unterminated string");
+            self.error("Unterminated string");
             return;
         }
 
@@ -123,7 +148,7 @@ impl Scanner {
         self.add_token_with_literal(TokenType::String, value);
     }
 
-    fn handle_multiline_string(&mut self) -> () {
+    fn handle_multiline_string(&mut self) {
         while self.peek() != '`' && !self.is_at_end() {
             if self.peek() == '\n' {
                 self.line += 1;
@@ -132,7 +157,8 @@ impl Scanner {
         }
 
         if self.is_at_end() {
-            error(self.line, "Unterminated string");
+            self.error("Unterminated string");
+            self.unterminated = true;
             return;
         }
 
@@ -147,23 +173,32 @@ impl Scanner {
         self.add_token_with_literal(TokenType::String, value);
     }
 
-    fn handle_multiline_comment(&mut self) -> () {
-        while self.peek() != '*' && self.peek_next() != '/' && !self.is_at_end() {
+    fn handle_multiline_comment(&mut self) {
+        // The opening '*'.
+        self.advance();
+
+        while !self.is_at_end() && (self.peek() != '*' || self.peek_next() != '/') {
             if self.peek() == '\n' {
                 self.line += 1;
             }
             self.advance();
         }
 
+        if self.is_at_end() {
+            self.error("Unterminated comment");
+            self.unterminated = true;
+            return;
+        }
+
         self.advance();
         self.advance();
     }
     
     fn is_a_digit(character: char) -> bool {
-        return character.is_digit(10);
+        character.is_ascii_digit()
     }
 
-    fn handle_number(&mut self) -> () {
+    fn handle_number(&mut self) {
         let mut is_float = false;
 
         while Scanner::is_a_digit(self.peek()) {
@@ -195,14 +230,14 @@ impl Scanner {
     }
 
     fn is_alpha(character: char) -> bool {
-        return character.is_alphabetic() || character == '_';
+        character.is_alphabetic() || character == '_'
     }
 
     fn is_alphanumeric(character: char) -> bool {
-      return Scanner::is_a_digit(character) || Scanner::is_alpha(character);
+      Scanner::is_a_digit(character) || Scanner::is_alpha(character)
     }
 
-    fn handle_identifier(&mut self) -> () {
+    fn handle_identifier(&mut self) {
       while Scanner::is_alphanumeric(self.peek()) {
         self.advance();
       }
@@ -214,11 +249,11 @@ impl Scanner {
         .take((self.current - self.start) as usize)
         .collect::<String>();
 
-      let type_ = self.get_keywords().get(&text).unwrap_or(&TokenType::Identifier).clone();
+      let type_ = *self.get_keywords().get(&text).unwrap_or(&TokenType::Identifier);
       self.add_token(type_);
     }
 
-    fn scan_token(&mut self) -> () {
+    fn scan_token(&mut self) {
         let c = self.advance();
 
         match c {
@@ -226,6 +261,9 @@ impl Scanner {
             ')' => self.add_token_with_literal(TokenType::RightParen, String::from(")")),
             '{' => self.add_token_with_literal(TokenType::LeftBrace, String::from("{")),
             '}' => self.add_token_with_literal(TokenType::RightBrace, String::from("}")),
+            '[' => self.add_token_with_literal(TokenType::LeftBracket, String::from("[")),
+            ']' => self.add_token_with_literal(TokenType::RightBracket, String::from("]")),
+            ':' => self.add_token_with_literal(TokenType::Colon, String::from(":")),
             ',' => self.add_token_with_literal(TokenType::Comma, String::from(",")),
             '.' => self.add_token_with_literal(TokenType::Dot, String::from(".")),
             '-' => self.add_token_with_literal(TokenType::Minus, String::from("-")),
@@ -296,7 +334,7 @@ impl Scanner {
                 } else if Scanner::is_alpha(c) {
                     self.handle_identifier();
                 } else {
-                    error(self.line, "Unexpected character found");
+                    self.error("Unexpected character found");
                 }
             }
         }
@@ -312,9 +350,10 @@ impl Scanner {
             TokenType::Eof,
             String::from(""),
             String::new(),
-            self.line.into(),
+            self.line,
         ));
+        self.spans.push((self.length, self.length));
 
-        return self.tokens.clone();
+        self.tokens.clone()
     }
 }
@@ -0,0 +1,153 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    hash::{BuildHasherDefault, Hasher},
+    rc::Rc,
+};
+
+/// An interned identifier.
+///
+/// The scanner interns every identifier it reads, so the parser and the
+/// environments only ever pass around and hash a small integer. Symbols are
+/// never freed; a program only has so many distinct names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Symbol(u32);
+
+struct Interner {
+    symbols: HashMap<Rc<str>, Symbol>,
+    names: Vec<Rc<str>>,
+}
+
+thread_local! {
+    static INTERNER: RefCell<Interner> = RefCell::new(Interner::new());
+}
+
+impl Interner {
+    fn new() -> Interner {
+        let mut interner = Interner {
+            symbols: HashMap::new(),
+            names: Vec::new(),
+        };
+
+        // `Symbol::default()` stands for the empty name.
+        interner.intern("");
+        interner
+    }
+
+    fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(name) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(self.names.len() as u32);
+        let name: Rc<str> = Rc::from(name);
+        self.names.push(name.clone());
+        self.symbols.insert(name, symbol);
+
+        symbol
+    }
+}
+
+impl Symbol {
+    pub fn intern(name: &str) -> Symbol {
+        INTERNER.with(|interner| interner.borrow_mut().intern(name))
+    }
+
+    /// The name this symbol was interned from.
+    pub fn name(&self) -> Rc<str> {
+        INTERNER.with(|interner| interner.borrow().names[self.0 as usize].clone())
+    }
+}
+
+/// A hash map keyed by symbols. Symbols are already unique integers, so a
+/// single multiply spreads them well enough and is far cheaper than SipHash.
+pub type SymbolMap<V> = HashMap<Symbol, V, BuildHasherDefault<SymbolHasher>>;
+
+#[derive(Default)]
+pub struct SymbolHasher(u64);
+
+impl Hasher for SymbolHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0.rotate_left(5) ^ *byte as u64).wrapping_mul(0x517c_c1b7_2722_0a95);
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.0 = (value as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        hash::{BuildHasher, BuildHasherDefault},
+        thread,
+    };
+
+    use super::{Symbol, SymbolHasher, SymbolMap};
+
+    fn hash(symbol: Symbol) -> u64 {
+        BuildHasherDefault::<SymbolHasher>::default().hash_one(symbol)
+    }
+
+    #[test]
+    fn names_round_trip() {
+        let taco = Symbol::intern("taco");
+        assert_eq!(Symbol::intern("taco"), taco);
+        assert_ne!(Symbol::intern("tacos"), taco);
+        assert_eq!(&*taco.name(), "taco");
+        assert_eq!(&*Symbol::intern("héllo").name(), "héllo");
+        assert_eq!(Symbol::intern(""), Symbol::default());
+        assert_eq!(&*Symbol::default().name(), "");
+    }
+
+    #[test]
+    fn each_thread_has_its_own_symbols() {
+        Symbol::intern("here first");
+        let here = Symbol::intern("shared name");
+
+        let there = thread::spawn(|| {
+            // The first name a new thread interns gets the first free symbol.
+            let there = Symbol::intern("shared name");
+            assert_eq!(there, Symbol(1));
+            assert_eq!(&*there.name(), "shared name");
+            there
+        })
+        .join()
+        .unwrap();
+
+        assert_ne!(here, there);
+        assert_eq!(&*here.name(), "shared name");
+    }
+
+    #[test]
+    fn symbols_hash_without_collisions() {
+        let hashes: HashSet<u64> = (0..100_000).map(|id| hash(Symbol(id))).collect();
+        assert_eq!(hashes.len(), 100_000);
+
+        // Hash tables pick buckets from the low bits and tags from the top
+        // ones, so both have to vary between neighbouring symbols.
+        let low: HashSet<u64> = (0..1024).map(|id| hash(Symbol(id)) & 1023).collect();
+        let high: HashSet<u64> = (0..1024).map(|id| hash(Symbol(id)) >> 57).collect();
+        assert!(low.len() > 512, "{} low bit patterns", low.len());
+        assert_eq!(high.len(), 128);
+    }
+
+    #[test]
+    fn symbol_maps_find_every_key() {
+        let mut map = SymbolMap::default();
+        for id in 0..10_000 {
+            map.insert(Symbol(id), id);
+        }
+
+        assert_eq!(map.len(), 10_000);
+        assert!((0..10_000).all(|id| map.get(&Symbol(id)) == Some(&id)));
+        assert_eq!(map.get(&Symbol(10_000)), None);
+    }
+}
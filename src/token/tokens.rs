@@ -1,3 +1,7 @@
+use std::fmt::{Display, Formatter};
+
+use crate::token::symbol::Symbol;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TokenType {
     // Single-character tokens.
@@ -5,6 +9,9 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
     Comma,
     Dot,
     Minus,
@@ -46,6 +53,12 @@ pub enum TokenType {
     True,
     Let,
     While,
+    Import,
+    Export,
+    Try,
+    Catch,
+    Finally,
+    Throw,
 
     Eof,
 }
@@ -56,27 +69,28 @@ pub struct Token {
     pub lexeme: String,
     pub literal: String,
     pub line: u32,
+    /// The interned lexeme of an identifier; the empty symbol otherwise.
+    pub symbol: Symbol,
 }
 
 impl Token {
     pub fn new(token_type: TokenType, lexeme: String, literal: String, line: u32) -> Token {
+        let symbol = match token_type {
+            TokenType::Identifier => Symbol::intern(&lexeme),
+            _ => Symbol::default(),
+        };
+
         Token {
             token_type,
             lexeme,
             literal,
             line,
+            symbol,
         }
     }
 
-    pub fn to_string(&mut self) -> String {
-        return String::from(format!(
-            "{:#?}: {} - {}",
-            self.token_type, self.lexeme, self.literal
-        ));
-    }
-
     pub fn as_string(&mut self) -> String {
-        return String::from(format!("{:#?}", self.lexeme));
+        format!("{:#?}", self.lexeme)
     }
 
     pub fn get_token_type(&self) -> &TokenType {
@@ -91,7 +105,17 @@ impl Token {
         &self.literal
     }
 
+    pub fn get_symbol(&self) -> Symbol {
+        self.symbol
+    }
+
     pub fn get_line(&self) -> u32 {
         self.line
     }
 }
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#?}: {} - {}", self.token_type, self.lexeme, self.literal)
+    }
+}
@@ -0,0 +1,26 @@
+/// Resolves a possibly negative index against a sequence of `len` items,
+/// counting from the end when negative. Returns `None` when out of range.
+pub fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let resolved = if index < 0 { index + len as i64 } else { index };
+
+    if resolved < 0 || resolved >= len as i64 {
+        None
+    } else {
+        Some(resolved as usize)
+    }
+}
+
+/// Resolves slice bounds the way Python does: missing bounds default to the
+/// ends of the sequence, negative bounds count from the end and everything
+/// is clamped to the sequence, so slicing never fails.
+pub fn slice_bounds(len: usize, start: Option<i64>, end: Option<i64>) -> (usize, usize) {
+    let clamp = |bound: i64| {
+        let resolved = if bound < 0 { bound + len as i64 } else { bound };
+        resolved.clamp(0, len as i64) as usize
+    };
+
+    let start = start.map(clamp).unwrap_or(0);
+    let end = end.map(clamp).unwrap_or(len);
+
+    (start, end.max(start))
+}
@@ -0,0 +1,148 @@
+use std::{
+    fmt::{Display, Formatter},
+    rc::Rc,
+};
+
+use crate::{
+    interpreter::interpreter::Value,
+    syntax::{function::FunctionStatement, import::ImportStatement},
+    token::tokens::Token,
+};
+
+/// A single VM instruction. Operands index into the pools of the chunk the
+/// instruction belongs to; jump operands are instruction offsets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpCode {
+    /// Pushes `constants[n]`.
+    Constant(u32),
+    Nil,
+    True,
+    False,
+    Pop,
+    /// Pops a value and prints it.
+    Print,
+    /// Pops a value and defines `tokens[n]` as it in the current scope.
+    DefineVariable(u32),
+    GetVariable(u32),
+    /// Assigns the value on top of the stack to `tokens[n]`, leaving it there.
+    SetVariable(u32),
+    /// Applies the unary operator `tokens[n]` to the top of the stack.
+    Unary(u32),
+    /// Pops two operands and applies the binary operator `tokens[n]`.
+    Binary(u32),
+    Jump(u32),
+    /// Jumps when the top of the stack is falsy, without popping it.
+    JumpIfFalse(u32),
+    /// Jumps when the top of the stack is truthy, without popping it.
+    JumpIfTrue(u32),
+    PushScope,
+    PopScope,
+    /// Pops `n` elements into a new list.
+    List(u32),
+    /// Fails unless the top of the stack can be a map key.
+    CheckKey(u32),
+    /// Pops `n` key/value pairs into a new map.
+    Map(u32),
+    Index(u32),
+    SetIndex(u32),
+    /// Pops an object and both bounds, `nil` standing for a missing bound.
+    Slice(u32),
+    /// Calls the callee below `argc` arguments; the token is the call's `(`.
+    Call(u32, u32),
+    GetProperty(u32),
+    /// Defines `functions[n]`, closed over the current scope.
+    Function(u32),
+    /// Pops the return value and leaves the chunk.
+    Return,
+    Import(u32),
+    /// Exports `tokens[n]`, which has just been declared.
+    Export(u32),
+    /// Pops a value and raises it; the token is the `throw` keyword.
+    Throw(u32),
+    Try(u32),
+}
+
+/// A function declaration together with its compiled body.
+#[derive(Debug)]
+pub struct FunctionProto {
+    pub declaration: FunctionStatement,
+    pub chunk: Rc<Chunk>,
+}
+
+/// A try statement. Each clause is compiled to its own chunk so the VM can
+/// run them the same way the tree-walker runs their blocks.
+#[derive(Debug)]
+pub struct TryBlock {
+    pub body: Chunk,
+    pub catch: Option<(Option<Token>, Chunk)>,
+    pub finally: Option<Chunk>,
+}
+
+/// A compiled sequence of instructions and the data they refer to.
+#[derive(Debug, Default)]
+pub struct Chunk {
+    pub code: Vec<OpCode>,
+    pub constants: Vec<Value>,
+    pub tokens: Vec<Token>,
+    pub functions: Vec<FunctionProto>,
+    pub imports: Vec<ImportStatement>,
+    pub tries: Vec<TryBlock>,
+    // Run-length encoded line table: (first instruction offset, line).
+    lines: Vec<(usize, u32)>,
+}
+
+impl Chunk {
+    pub fn new() -> Chunk {
+        Chunk::default()
+    }
+
+    /// Appends `op`, returning its offset.
+    pub fn write(&mut self, op: OpCode, line: u32) -> usize {
+        let offset = self.code.len();
+        self.code.push(op);
+
+        match self.lines.last() {
+            Some((_, last)) if *last == line => {}
+            _ => self.lines.push((offset, line)),
+        }
+
+        offset
+    }
+
+    /// The source line the instruction at `offset` was compiled from.
+    pub fn line(&self, offset: usize) -> u32 {
+        let run = self.lines.partition_point(|(start, _)| *start <= offset);
+        match run {
+            0 => 0,
+            run => self.lines[run - 1].1,
+        }
+    }
+}
+
+impl Display for Chunk {
+    /// Disassembles the chunk, one instruction per line.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (offset, op) in self.code.iter().enumerate() {
+            let detail = match op {
+                OpCode::Constant(index) => format!("{:?}", self.constants[*index as usize]),
+                OpCode::DefineVariable(index)
+                | OpCode::GetVariable(index)
+                | OpCode::SetVariable(index)
+                | OpCode::Unary(index)
+                | OpCode::Binary(index)
+                | OpCode::GetProperty(index)
+                | OpCode::Export(index) => self.tokens[*index as usize].get_lexeme().to_string(),
+                OpCode::Function(index) => self.functions[*index as usize]
+                    .declaration
+                    .name
+                    .get_lexeme()
+                    .to_string(),
+                _ => String::new(),
+            };
+
+            writeln!(f, "{:04} {:>4} {:?} {}", offset, self.line(offset), op, detail)?;
+        }
+
+        Ok(())
+    }
+}
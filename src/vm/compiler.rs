@@ -0,0 +1,274 @@
+use std::rc::Rc;
+
+use crate::{
+    core::{expression::Expr, literal::Literal},
+    interpreter::interpreter::Value,
+    syntax::statement::Statement,
+    token::tokens::{Token, TokenType},
+    vm::chunk::{Chunk, FunctionProto, OpCode, TryBlock},
+};
+
+/// Compiles statements into a chunk for the VM.
+///
+/// Variables are still looked up by name in environments, exactly as the
+/// tree-walker does, so both backends agree on scoping and closures.
+pub struct Compiler {
+    chunk: Chunk,
+    // The line of the most recent token seen, recorded against each
+    // instruction in the chunk's line table.
+    line: u32,
+}
+
+impl Compiler {
+    pub fn compile(statements: &[Statement]) -> Chunk {
+        let mut compiler = Compiler {
+            chunk: Chunk::new(),
+            line: 0,
+        };
+
+        for statement in statements {
+            compiler.statement(statement);
+        }
+
+        compiler.chunk
+    }
+
+    fn emit(&mut self, op: OpCode) -> usize {
+        self.chunk.write(op, self.line)
+    }
+
+    fn constant(&mut self, value: Value) -> u32 {
+        self.chunk.constants.push(value);
+        (self.chunk.constants.len() - 1) as u32
+    }
+
+    fn token(&mut self, token: &Token) -> u32 {
+        self.line = token.get_line();
+        self.chunk.tokens.push(token.clone());
+        (self.chunk.tokens.len() - 1) as u32
+    }
+
+    /// Emits a jump whose target is filled in later by `patch`.
+    fn jump(&mut self, op: fn(u32) -> OpCode) -> usize {
+        self.emit(op(0))
+    }
+
+    fn patch(&mut self, offset: usize) {
+        let target = self.chunk.code.len() as u32;
+        self.chunk.code[offset] = match self.chunk.code[offset] {
+            OpCode::Jump(_) => OpCode::Jump(target),
+            OpCode::JumpIfFalse(_) => OpCode::JumpIfFalse(target),
+            OpCode::JumpIfTrue(_) => OpCode::JumpIfTrue(target),
+            op => op,
+        };
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::LetStatement(declaration) => {
+                self.expression(&declaration.initializer);
+                let name = self.token(&declaration.name);
+                self.emit(OpCode::DefineVariable(name));
+            }
+            Statement::ExpressionStatement(statement) => {
+                self.expression(&statement.expression);
+                self.emit(OpCode::Pop);
+            }
+            Statement::PrintStatement(statement) => {
+                self.expression(&statement.expression);
+                self.emit(OpCode::Print);
+            }
+            Statement::BlockStatement(block) => {
+                self.emit(OpCode::PushScope);
+                for statement in &block.statements {
+                    self.statement(statement);
+                }
+                self.emit(OpCode::PopScope);
+            }
+            Statement::ImportStatement(import) => {
+                self.line = import.keyword.get_line();
+                self.chunk.imports.push(import.clone());
+                let index = (self.chunk.imports.len() - 1) as u32;
+                self.emit(OpCode::Import(index));
+            }
+            Statement::ExportStatement(export) => {
+                self.statement(&export.declaration);
+
+                let name = match export.declaration.as_ref() {
+                    Statement::LetStatement(declaration) => Some(&declaration.name),
+                    Statement::FunctionStatement(declaration) => Some(&declaration.name),
+                    _ => None,
+                };
+                if let Some(name) = name {
+                    let name = self.token(name);
+                    self.emit(OpCode::Export(name));
+                }
+            }
+            Statement::ThrowStatement(throw) => {
+                self.expression(&throw.value);
+                let keyword = self.token(&throw.keyword);
+                self.emit(OpCode::Throw(keyword));
+            }
+            Statement::TryStatement(statement) => {
+                let block = TryBlock {
+                    body: Compiler::compile(&statement.body),
+                    catch: statement.catch.as_ref().map(|catch| {
+                        (catch.name.clone(), Compiler::compile(&catch.body))
+                    }),
+                    finally: statement
+                        .finally
+                        .as_ref()
+                        .map(|finally| Compiler::compile(finally)),
+                };
+
+                self.chunk.tries.push(block);
+                let index = (self.chunk.tries.len() - 1) as u32;
+                self.emit(OpCode::Try(index));
+            }
+            Statement::FunctionStatement(declaration) => {
+                self.line = declaration.name.get_line();
+                self.chunk.functions.push(FunctionProto {
+                    declaration: declaration.clone(),
+                    chunk: Rc::new(Compiler::compile(&declaration.body)),
+                });
+                let index = (self.chunk.functions.len() - 1) as u32;
+                self.emit(OpCode::Function(index));
+            }
+            Statement::ReturnStatement(statement) => {
+                self.line = statement.keyword.get_line();
+                match &statement.value {
+                    Some(value) => self.expression(value),
+                    None => {
+                        self.emit(OpCode::Nil);
+                    }
+                }
+                self.emit(OpCode::Return);
+            }
+            Statement::IfStatement(statement) => {
+                self.expression(&statement.condition);
+                let else_jump = self.jump(OpCode::JumpIfFalse);
+                self.emit(OpCode::Pop);
+                self.statement(&statement.then_branch);
+
+                let end_jump = self.jump(OpCode::Jump);
+                self.patch(else_jump);
+                self.emit(OpCode::Pop);
+                if let Some(else_branch) = &statement.else_branch {
+                    self.statement(else_branch);
+                }
+                self.patch(end_jump);
+            }
+            Statement::WhileStatement(statement) => {
+                let start = self.chunk.code.len() as u32;
+                self.expression(&statement.condition);
+                let exit_jump = self.jump(OpCode::JumpIfFalse);
+                self.emit(OpCode::Pop);
+                self.statement(&statement.body);
+                self.emit(OpCode::Jump(start));
+
+                self.patch(exit_jump);
+                self.emit(OpCode::Pop);
+            }
+        }
+    }
+
+    fn expression(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Literal(literal) => {
+                let op = match literal {
+                    Literal::Nil => OpCode::Nil,
+                    Literal::Boolean(true) => OpCode::True,
+                    Literal::Boolean(false) => OpCode::False,
+                    literal => OpCode::Constant(self.constant(Value::from(literal.clone()))),
+                };
+                self.emit(op);
+            }
+            Expr::GroupingExpr(grouping) => self.expression(&grouping.expr),
+            Expr::UnaryExpr(unary) => {
+                self.expression(unary.get_right());
+                let operator = self.token(unary.get_operator());
+                self.emit(OpCode::Unary(operator));
+            }
+            Expr::BinaryExpr(binary) => {
+                self.expression(binary.get_left());
+                self.expression(binary.get_right());
+                let operator = self.token(binary.get_operator());
+                self.emit(OpCode::Binary(operator));
+            }
+            Expr::LogicalExpr(logical) => {
+                self.expression(logical.get_left());
+                self.line = logical.get_operator().get_line();
+                let end_jump = match logical.get_operator().token_type {
+                    TokenType::Or => self.jump(OpCode::JumpIfTrue),
+                    _ => self.jump(OpCode::JumpIfFalse),
+                };
+                self.emit(OpCode::Pop);
+                self.expression(logical.get_right());
+                self.patch(end_jump);
+            }
+            Expr::VarDeclaration(name) => {
+                let name = self.token(name);
+                self.emit(OpCode::GetVariable(name));
+            }
+            Expr::AssignExpr(assign) => {
+                self.expression(assign.get_value());
+                let name = self.token(assign.get_name());
+                self.emit(OpCode::SetVariable(name));
+            }
+            Expr::MapExpr(map) => {
+                let brace = self.token(map.get_brace());
+                for (key, value) in map.get_entries() {
+                    self.expression(key);
+                    self.emit(OpCode::CheckKey(brace));
+                    self.expression(value);
+                }
+                self.emit(OpCode::Map(map.get_entries().len() as u32));
+            }
+            Expr::IndexExpr(index) => {
+                self.expression(index.get_object());
+                self.expression(index.get_index());
+                let bracket = self.token(index.get_bracket());
+                self.emit(OpCode::Index(bracket));
+            }
+            Expr::SetIndexExpr(set) => {
+                self.expression(set.get_object());
+                self.expression(set.get_index());
+                self.expression(set.get_value());
+                let bracket = self.token(set.get_bracket());
+                self.emit(OpCode::SetIndex(bracket));
+            }
+            Expr::CallExpr(call) => {
+                self.expression(call.get_callee());
+                for argument in call.get_arguments() {
+                    self.expression(argument);
+                }
+                let paren = self.token(call.get_paren());
+                self.emit(OpCode::Call(call.get_arguments().len() as u32, paren));
+            }
+            Expr::GetExpr(get) => {
+                self.expression(get.get_object());
+                let name = self.token(get.get_name());
+                self.emit(OpCode::GetProperty(name));
+            }
+            Expr::ListExpr(list) => {
+                for element in list.get_elements() {
+                    self.expression(element);
+                }
+                self.emit(OpCode::List(list.get_elements().len() as u32));
+            }
+            Expr::SliceExpr(slice) => {
+                self.expression(slice.get_object());
+                for bound in [slice.get_start(), slice.get_end()] {
+                    match bound {
+                        Some(bound) => self.expression(bound),
+                        None => {
+                            self.emit(OpCode::Nil);
+                        }
+                    }
+                }
+                let bracket = self.token(slice.get_bracket());
+                self.emit(OpCode::Slice(bracket));
+            }
+        }
+    }
+}
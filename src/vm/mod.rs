@@ -0,0 +1,4 @@
+pub mod chunk;
+pub mod compiler;
+#[allow(clippy::module_inception)]
+pub mod vm;
\ No newline at end of file
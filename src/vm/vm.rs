@@ -0,0 +1,229 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    environment::environment::Environment,
+    error::interpreter::RuntimeError,
+    interpreter::{
+        interpreter::{Flow, Interpreter, Value},
+        map::{HashKey, ValueMap},
+        operators,
+    },
+    vm::chunk::{Chunk, OpCode, TryBlock},
+};
+
+fn new_scope(environment: &Rc<RefCell<Environment>>) -> Rc<RefCell<Environment>> {
+    Rc::new(RefCell::new(Environment::new_enclosing(environment.clone())))
+}
+
+impl Interpreter {
+    /// Runs a compiled chunk in `environment`. Calls and imports go through
+    /// the same code paths as the tree-walker, so the two backends behave
+    /// identically; only the way each statement is dispatched differs.
+    pub(crate) fn run_chunk(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        chunk: &Chunk,
+    ) -> Result<Flow, RuntimeError> {
+        let mut stack = Vec::<Value>::new();
+        // Enclosing scopes of the blocks currently open in this chunk.
+        let mut scopes = Vec::<Rc<RefCell<Environment>>>::new();
+        let mut environment = environment.clone();
+        let mut ip = 0;
+
+        // The compiler only emits balanced code, so an empty stack here
+        // would be a compiler bug rather than something a script can cause.
+        macro_rules! pop {
+            () => {
+                stack.pop().expect("VM stack underflow")
+            };
+        }
+
+        while let Some(op) = chunk.code.get(ip) {
+            ip += 1;
+            self.step(|| Some(chunk.line(ip - 1)))?;
+
+            match *op {
+                OpCode::Constant(index) => stack.push(chunk.constants[index as usize].clone()),
+                OpCode::Nil => stack.push(Value::Nil),
+                OpCode::True => stack.push(Value::Boolean(true)),
+                OpCode::False => stack.push(Value::Boolean(false)),
+                OpCode::Pop => {
+                    pop!();
+                }
+                OpCode::Print => {
+                    let value = pop!();
+                    self.print(value, Some(chunk.line(ip - 1)))?;
+                }
+                OpCode::DefineVariable(name) => {
+                    let value = pop!();
+                    let name = chunk.tokens[name as usize].get_symbol();
+                    environment.borrow_mut().define(name, value);
+                }
+                OpCode::GetVariable(name) => {
+                    let value = environment.borrow().get(&chunk.tokens[name as usize])?;
+                    stack.push(value);
+                }
+                OpCode::SetVariable(name) => {
+                    let value = pop!();
+                    environment
+                        .borrow_mut()
+                        .assign(&chunk.tokens[name as usize], value.clone())?;
+                    stack.push(value);
+                }
+                OpCode::Unary(operator) => {
+                    let right = pop!();
+                    stack.push(operators::unary(&chunk.tokens[operator as usize], right)?);
+                }
+                OpCode::Binary(operator) => {
+                    let right = pop!();
+                    let left = pop!();
+                    let operator = &chunk.tokens[operator as usize];
+                    let value = operators::binary(operator, left, right)?;
+                    self.allocate(&value, Some(operator.get_line()))?;
+                    stack.push(value);
+                }
+                OpCode::Jump(target) => {
+                    // Jumping backwards closes a loop iteration.
+                    if (target as usize) < ip {
+                        self.maybe_collect();
+                    }
+
+                    ip = target as usize;
+                }
+                OpCode::JumpIfFalse(target) => {
+                    if !stack.last().is_some_and(Value::is_truthy) {
+                        ip = target as usize;
+                    }
+                }
+                OpCode::JumpIfTrue(target) => {
+                    if stack.last().is_some_and(Value::is_truthy) {
+                        ip = target as usize;
+                    }
+                }
+                OpCode::PushScope => {
+                    let scope = new_scope(&environment);
+                    scopes.push(std::mem::replace(&mut environment, scope));
+                }
+                OpCode::PopScope => {
+                    environment = scopes.pop().expect("VM scope underflow");
+                }
+                OpCode::List(count) => {
+                    let elements = stack.split_off(stack.len() - count as usize);
+                    let value = Value::list(elements);
+                    self.allocate(&value, Some(chunk.line(ip - 1)))?;
+                    stack.push(value);
+                }
+                OpCode::CheckKey(brace) => {
+                    if let Some(key) = stack.last() {
+                        self.hash_key(&chunk.tokens[brace as usize], key)?;
+                    }
+                }
+                OpCode::Map(count) => {
+                    let entries = stack.split_off(stack.len() - 2 * count as usize);
+                    let mut map = ValueMap::new();
+
+                    let mut entries = entries.into_iter();
+                    while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                        // Keys were checked by `CheckKey` as they were evaluated.
+                        if let Some(key) = HashKey::from_value(&key) {
+                            map.insert(key, value);
+                        }
+                    }
+
+                    let value = Value::map(map);
+                    self.allocate(&value, Some(chunk.line(ip - 1)))?;
+                    stack.push(value);
+                }
+                OpCode::Index(bracket) => {
+                    let key = pop!();
+                    let object = pop!();
+                    stack.push(self.index(&chunk.tokens[bracket as usize], object, key)?);
+                }
+                OpCode::SetIndex(bracket) => {
+                    let value = pop!();
+                    let key = pop!();
+                    let object = pop!();
+                    let bracket = &chunk.tokens[bracket as usize];
+                    self.allocate_entry(&object, bracket.get_line())?;
+                    stack.push(self.set_index(bracket, object, key, value)?);
+                }
+                OpCode::Slice(bracket) => {
+                    let end = pop!();
+                    let start = pop!();
+                    let object = pop!();
+                    let bracket = &chunk.tokens[bracket as usize];
+                    let value = self.slice(bracket, object, Some(start), Some(end))?;
+                    self.allocate(&value, Some(bracket.get_line()))?;
+                    stack.push(value);
+                }
+                OpCode::Call(argc, paren) => {
+                    let arguments = stack.split_off(stack.len() - argc as usize);
+                    let callee = pop!();
+                    stack.push(self.call(&chunk.tokens[paren as usize], callee, arguments)?);
+                }
+                OpCode::GetProperty(name) => {
+                    let object = pop!();
+                    stack.push(self.get_property(&chunk.tokens[name as usize], object)?);
+                }
+                OpCode::Function(index) => {
+                    let proto = &chunk.functions[index as usize];
+                    self.define_function(
+                        &environment,
+                        proto.declaration.clone(),
+                        Some(proto.chunk.clone()),
+                    );
+                }
+                OpCode::Return => return Ok(Flow::Return(pop!())),
+                OpCode::Import(index) => {
+                    self.bind_import(&environment, &chunk.imports[index as usize])?;
+                }
+                OpCode::Export(name) => self.export(chunk.tokens[name as usize].clone()),
+                OpCode::Throw(keyword) => {
+                    let value = pop!();
+                    return Err(self.throw(&chunk.tokens[keyword as usize], value));
+                }
+                OpCode::Try(index) => {
+                    if let Flow::Return(value) = self.run_try(&environment, &chunk.tries[index as usize])? {
+                        return Ok(Flow::Return(value));
+                    }
+                }
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    fn run_try(
+        &mut self,
+        environment: &Rc<RefCell<Environment>>,
+        block: &TryBlock,
+    ) -> Result<Flow, RuntimeError> {
+        let mut result = self.run_chunk(&new_scope(environment), &block.body);
+
+        // Limits stop the script outright.
+        if matches!(&result, Err(error) if error.get_kind().is_limit()) {
+            return result;
+        }
+
+        if let (Err(error), Some((name, body))) = (&result, &block.catch) {
+            let scope = new_scope(environment);
+            if let Some(name) = name {
+                scope
+                    .borrow_mut()
+                    .define(name.get_symbol(), error.to_value());
+            }
+
+            result = self.run_chunk(&new_scope(&scope), body);
+        }
+
+        // An error or return in the finally block replaces whatever the rest
+        // of the statement was doing.
+        if let Some(finally) = &block.finally {
+            if let Flow::Return(value) = self.run_chunk(&new_scope(environment), finally)? {
+                return Ok(Flow::Return(value));
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,100 @@
+use taco::{
+    interpreter::{
+        interpreter::Backend,
+        output::{Buffer, Output},
+    },
+    Engine, Error,
+};
+
+/// Runs `source` on one backend and returns what it printed, followed by
+/// its result, or its error and traceback.
+fn run(backend: Backend, source: &str) -> String {
+    let buffer = Buffer::new();
+    let mut engine = Engine::with_output(Output::new(buffer.clone(), buffer.clone()));
+    engine.interpreter().set_backend(backend);
+    let outcome = match engine.eval(source) {
+        Ok(value) => format!("=> {:?}", value),
+        Err(Error::Runtime(error)) => {
+            let trace: Vec<String> = error.get_trace().iter().map(|frame| frame.to_string()).collect();
+            format!("error: {}\n{}", error, trace.join("\n"))
+        }
+        Err(error) => format!("error: {}", error),
+    };
+    buffer.contents() + &outcome
+}
+
+fn assert_same(source: &str) {
+    let tree = run(Backend::Tree, source);
+    let vm = run(Backend::Vm, source);
+    assert_eq!(tree, vm, "backends disagree on:\n{}", source);
+}
+
+#[test]
+fn closures() {
+    assert_same(
+        "taco counter() { let c = 0; taco inc() { c = c + 1; return c; } return inc; }
+         let a = counter(); let b = counter();
+         a(); a(); b();
+         print a(); print b();
+         taco adder(n) { taco add(x) { return x + n; } return add; }
+         let fs = [adder(1), adder(10), adder(100)];
+         print fs[0](1) + fs[1](1) + fs[2](1);
+         taco outer() { let x = 1; taco middle() { taco inner() { x = x * 2; return x; } return inner; } return middle(); }
+         let f = outer(); f(); print f();",
+    );
+}
+
+#[test]
+fn try_catch_finally() {
+    assert_same(
+        r#"taco t() { try { throw "boom"; } catch (e) { print "caught " + e; return 1; } finally { print "finally"; } }
+           print t();
+           taco u() { try { return "body"; } finally { print "cleanup"; } }
+           print u();
+           try { try { throw 1; } finally { print "inner"; } } catch (e) { print e; }
+           taco loop() { let n = 0; while (true) { n = n + 1; try { if (n > 2) return n; } finally { print n; } } }
+           print loop();"#,
+    );
+}
+
+#[test]
+fn early_return() {
+    assert_same(
+        "taco find(xs, x) { let i = 0; while (i < len(xs)) { if (xs[i] == x) return i; i = i + 1; } return -1; }
+         print find([4, 5, 6], 6); print find([4, 5, 6], 7);
+         taco nothing() { return; }
+         print nothing();
+         taco fib(n) { if (n < 2) return n; return fib(n - 1) + fib(n - 2); }
+         fib(12);",
+    );
+}
+
+#[test]
+fn slicing() {
+    assert_same(
+        r#"let xs = [1, 2, 3, 4, 5];
+           print xs[1:3]; print xs[:2]; print xs[3:]; print xs[-2:]; print xs[:];
+           print "héllo"[1:3]; print "taco"[-2:];
+           print xs[4:1];"#,
+    );
+}
+
+#[test]
+fn error_messages() {
+    let scripts = [
+        "[1][5];",
+        r#"1 + "a";"#,
+        "undefined_name;",
+        "taco f(a) { return a; } f(1, 2);",
+        "let m = {}; m[[1]];",
+        "let m = {a: 1}; m.b.c;",
+        "taco deep() { return deep(); } deep();",
+        r#"throw "plain";"#,
+        "try { nil(); } catch (e) { print e.message; print e.line; }",
+        "int(\"x\");",
+        "taco a() {\n  return b();\n}\ntaco b() {\n  let x = nil;\n  return x.y;\n}\na();",
+    ];
+    for script in scripts {
+        assert_same(script);
+    }
+}
@@ -0,0 +1,52 @@
+use taco::{
+    error::interpreter::ErrorKind,
+    interpreter::interpreter::{Backend, DEFAULT_MAX_DEPTH},
+    Engine, Error, Value,
+};
+
+const COUNT_DOWN: &str = "taco down(n) { if (n == 0) { return 0; } return 1 + down(n - 1); }";
+
+fn engine(backend: Backend) -> Engine {
+    let mut engine = Engine::new();
+    engine.interpreter().set_backend(backend);
+    engine
+}
+
+// These run on the test harness's own threads, whose stacks are far smaller
+// than the default depth would need without growing them.
+
+#[test]
+fn runaway_recursion_is_a_stack_overflow_error() {
+    for backend in [Backend::Tree, Backend::Vm] {
+        let mut engine = engine(backend);
+        match engine.eval("taco forever(n) { return forever(n + 1); } forever(0);") {
+            Err(Error::Runtime(error)) => {
+                assert_eq!(error.get_kind(), ErrorKind::StackOverflow);
+                assert_eq!(error.get_message(), "Stack overflow.");
+            }
+            other => panic!("expected a stack overflow on {:?}, got {:?}", backend, other),
+        }
+    }
+}
+
+#[test]
+fn recursion_up_to_the_default_depth_succeeds() {
+    let depth = DEFAULT_MAX_DEPTH as i64 - 1;
+    for backend in [Backend::Tree, Backend::Vm] {
+        let mut engine = engine(backend);
+        engine.eval(COUNT_DOWN).unwrap();
+        let result = engine.call_function("down", vec![Value::Integer(depth)]).unwrap();
+        assert_eq!(result, Value::Integer(depth));
+    }
+}
+
+#[test]
+fn large_depth_limits_are_usable() {
+    for backend in [Backend::Tree, Backend::Vm] {
+        let mut engine = engine(backend);
+        engine.interpreter().set_max_depth(50_000);
+        engine.eval(COUNT_DOWN).unwrap();
+        let result = engine.call_function("down", vec![Value::Integer(40_000)]).unwrap();
+        assert_eq!(result, Value::Integer(40_000));
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use taco::{
+    interpreter::{
+        convert::{ConversionError, FromValue, IntoValue},
+        interpreter::Backend,
+    },
+    Engine, Error, Value,
+};
+
+fn round_trip<T: IntoValue + FromValue + Clone>(value: T) -> T {
+    T::from_value(value.into_value()).unwrap()
+}
+
+fn runtime_error(engine: &mut Engine, source: &str) -> String {
+    match engine.eval(source) {
+        Err(Error::Runtime(error)) => error.get_message().to_string(),
+        other => panic!("expected {:?} to fail, got {:?}", source, other),
+    }
+}
+
+#[test]
+fn values_round_trip() {
+    assert_eq!(round_trip(-42i64), -42);
+    assert_eq!(round_trip(1.5f64), 1.5);
+    assert!(round_trip(true));
+    assert_eq!(round_trip(String::from("héllo")), "héllo");
+    assert_eq!(round_trip(vec![1i64, 2, 3]), vec![1, 2, 3]);
+    assert_eq!(round_trip(vec![vec![String::from("a")], vec![]]), vec![vec![String::from("a")], vec![]]);
+    assert_eq!(round_trip(Some(7i64)), Some(7));
+    assert_eq!(round_trip(None::<i64>), None);
+    assert_eq!(round_trip(200u8), 200);
+
+    let map = HashMap::from([(String::from("a"), 1i64), (String::from("b"), 2)]);
+    assert_eq!(round_trip(map.clone()), map);
+}
+
+#[test]
+fn conversions_match_script_values() {
+    let mut engine = Engine::new();
+    assert_eq!(engine.eval("[1, 2];").unwrap(), Value::from(vec![1i64, 2]));
+    assert_eq!(engine.eval(r#""taco";"#).unwrap(), Value::from("taco"));
+    assert_eq!(engine.eval("nil;").unwrap(), Value::from(None::<bool>));
+    assert_eq!(f64::try_from(engine.eval("2;").unwrap()), Ok(2.0));
+    assert_eq!(
+        HashMap::<String, bool>::from_value(engine.eval("{yes: true};").unwrap()).unwrap(),
+        HashMap::from([(String::from("yes"), true)])
+    );
+}
+
+#[test]
+fn mismatched_types_name_both_sides() {
+    let error = |result: Result<(), ConversionError>| result.unwrap_err().to_string();
+
+    assert_eq!(error(i64::from_value(Value::from("1")).map(drop)), "Expected integer but got string.");
+    assert_eq!(error(bool::from_value(Value::Nil).map(drop)), "Expected boolean but got nil.");
+    assert_eq!(
+        error(Option::<String>::from_value(Value::Integer(1)).map(drop)),
+        "Expected string or nil but got integer."
+    );
+    assert_eq!(
+        error(Vec::<i64>::from_value(Value::from(vec!["a"])).map(drop)),
+        "Expected list of integer but got list containing string."
+    );
+    assert_eq!(
+        error(HashMap::<String, i64>::from_value(Value::from(HashMap::from([(String::from("a"), "b")]))).map(drop)),
+        "Expected map of integer but got map containing string."
+    );
+
+    let numeric_keys = Engine::new().eval("{1: 2};").unwrap();
+    assert_eq!(
+        error(HashMap::<String, i64>::from_value(numeric_keys).map(drop)),
+        "Expected map of integer but got map with integer key."
+    );
+}
+
+#[test]
+fn narrow_integers_are_range_checked() {
+    assert_eq!(u8::from_value(Value::Integer(255)), Ok(255));
+    assert_eq!(
+        u8::from_value(Value::Integer(300)).unwrap_err().to_string(),
+        "Expected integer from 0 to 255 but got 300."
+    );
+    assert_eq!(
+        usize::from_value(Value::Integer(-1)).unwrap_err().to_string(),
+        format!("Expected integer from 0 to {} but got -1.", usize::MAX)
+    );
+    assert_eq!(i32::from_value(Value::Integer(i64::from(i32::MIN))), Ok(i32::MIN));
+    assert!(i32::from_value(Value::Integer(i64::from(i32::MAX) + 1)).is_err());
+    assert_eq!(
+        u8::from_value(Value::Float(1.0)).unwrap_err().to_string(),
+        "Expected integer from 0 to 255 but got float."
+    );
+}
+
+#[test]
+fn host_functions_check_their_arguments() {
+    for backend in [Backend::Tree, Backend::Vm] {
+        let mut engine = Engine::new();
+        engine.interpreter().set_backend(backend);
+        engine.define_fn("repeat", |text: String, times: u8| text.repeat(usize::from(times)));
+        engine.define_fn("total", |numbers: Vec<f64>| numbers.iter().sum::<f64>());
+        engine.define_fn("half", |n: i64| match n % 2 {
+            0 => Ok(n / 2),
+            _ => Err(format!("{} is odd.", n)),
+        });
+
+        assert_eq!(engine.eval(r#"repeat("ab", 3);"#).unwrap(), Value::from("ababab"));
+        assert_eq!(engine.eval("total([1, 2.5]);").unwrap(), Value::Float(3.5));
+        assert_eq!(engine.eval("half(8);").unwrap(), Value::Integer(4));
+
+        assert_eq!(
+            runtime_error(&mut engine, r#"repeat(3, "ab");"#),
+            "repeat() expects string as argument 1 but got integer."
+        );
+        assert_eq!(
+            runtime_error(&mut engine, r#"repeat("ab", 300);"#),
+            "repeat() expects integer from 0 to 255 as argument 2 but got 300."
+        );
+        assert_eq!(
+            runtime_error(&mut engine, r#"total([1, "2"]);"#),
+            "total() expects list of float as argument 1 but got list containing string."
+        );
+        assert_eq!(runtime_error(&mut engine, "half(3);"), "3 is odd.");
+
+        assert_eq!(runtime_error(&mut engine, r#"repeat("ab");"#), "repeat() expected 2 arguments but got 1.");
+        assert_eq!(
+            runtime_error(&mut engine, r#"repeat("ab", 1, 2);"#),
+            "repeat() expected 2 arguments but got 3."
+        );
+        assert_eq!(runtime_error(&mut engine, "half();"), "half() expected 1 arguments but got 0.");
+    }
+}
@@ -0,0 +1,79 @@
+use taco::{
+    interpreter::{
+        interpreter::Interpreter,
+        output::{Buffer, Output},
+    },
+    lang::{repl::run_command, taco::Taco},
+};
+
+/// A `Taco` whose stdout and stderr are captured separately.
+fn taco(stdout: &Buffer, stderr: &Buffer) -> Taco {
+    let mut taco = Taco::new();
+    taco.interpreter = Some(Interpreter::with_output(Output::new(stdout.clone(), stderr.clone())));
+    taco
+}
+
+#[test]
+fn runtime_errors_and_tracebacks_go_to_the_stderr_sink() {
+    let (stdout, stderr) = (Buffer::new(), Buffer::new());
+    let mut taco = taco(&stdout, &stderr);
+
+    let source = "taco inner() {\n  return nil.x;\n}\ntaco outer() {\n  return inner();\n}\nprint 1;\nouter();";
+    assert_eq!(taco.run_source(String::from(source)), Err(70));
+
+    assert_eq!(stdout.contents(), "1\n");
+    assert_eq!(
+        stderr.contents(),
+        "[Line 2]: Undefined property 'x' on nil.\n\
+         Traceback (innermost first):\n  \
+         at inner (line 2)\n  \
+         at outer (line 5)\n  \
+         at <script> (line 8)\n"
+    );
+}
+
+#[test]
+fn syntax_errors_go_to_the_stderr_sink() {
+    let (stdout, stderr) = (Buffer::new(), Buffer::new());
+    let mut taco = taco(&stdout, &stderr);
+
+    assert_eq!(taco.run_source(String::from("let = 1;")), Err(65));
+    assert_eq!(taco.check(String::from("\n@")), Err(65));
+
+    assert_eq!(stdout.contents(), "");
+    assert!(stderr.contents().starts_with("[Line 1] Error  at '=': "));
+    assert!(stderr.contents().contains("[Line 2] Error : Unexpected character found"));
+}
+
+#[test]
+fn repl_commands_write_to_the_sinks() {
+    let (stdout, stderr) = (Buffer::new(), Buffer::new());
+    let mut taco = taco(&stdout, &stderr);
+    taco.run_source(String::from("let answer = 42;")).unwrap();
+
+    for command in ["help", "env", "type answer", "ast 1 + 2", "tokens 1", "bogus", "type nil()"] {
+        let _ = run_command(command, &mut taco);
+    }
+
+    let out = stdout.contents();
+    assert!(out.contains(":help"));
+    assert!(out.contains("answer = 42"));
+    assert!(out.contains("integer"));
+    assert!(out.contains("Binary"));
+    assert!(out.contains("Integer"));
+    let err = stderr.contents();
+    assert!(err.contains("Unknown command ':bogus'"));
+    assert!(err.contains("Cannot call a value of type nil."));
+}
+
+#[test]
+fn dumps_write_to_the_stdout_sink() {
+    let (stdout, stderr) = (Buffer::new(), Buffer::new());
+    let mut taco = taco(&stdout, &stderr);
+
+    taco.dump_tokens("print 1;").unwrap();
+    taco.dump_ast("print 1;").unwrap();
+
+    assert!(stdout.contents().contains("Print"));
+    assert_eq!(stderr.contents(), "");
+}
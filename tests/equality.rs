@@ -0,0 +1,90 @@
+use taco::{interpreter::interpreter::Backend, Engine, Value};
+
+fn eval(source: &str) -> Value {
+    Engine::new().eval(source).unwrap()
+}
+
+/// Evaluates `source` on both backends, with and without the optimizer,
+/// checking they agree.
+fn eval_everywhere(source: &str) -> Value {
+    let mut results = Vec::new();
+    for backend in [Backend::Tree, Backend::Vm] {
+        for optimize in [false, true] {
+            let mut engine = Engine::new();
+            engine.interpreter().set_backend(backend);
+            engine.interpreter().set_optimize(optimize);
+            results.push(engine.eval(source).unwrap());
+        }
+    }
+
+    let first = results[0].clone();
+    assert!(results.iter().all(|result| *result == first), "{:?} gave {:?}", source, results);
+    first
+}
+
+#[test]
+fn list_containing_itself_equals_itself() {
+    let source = "let l = [1]; l[0] = l;";
+    assert_eq!(eval(&format!("{} l == l;", source)), Value::Boolean(true));
+    assert_eq!(eval(&format!("{} l != l;", source)), Value::Boolean(false));
+}
+
+#[test]
+fn map_containing_itself_equals_itself() {
+    let source = r#"let m = {}; m["self"] = m;"#;
+    assert_eq!(eval(&format!("{} m == m;", source)), Value::Boolean(true));
+    assert_eq!(eval(&format!("{} m != m;", source)), Value::Boolean(false));
+}
+
+#[test]
+fn distinct_self_referential_containers_compare_by_shape() {
+    let lists = "let a = [1]; a[0] = a; let b = [1]; b[0] = b;";
+    assert_eq!(eval(&format!("{} a == b;", lists)), Value::Boolean(true));
+    assert_eq!(eval(&format!("{} a == [a, 1];", lists)), Value::Boolean(false));
+
+    let maps = r#"let a = {}; a["x"] = a; let b = {}; b["x"] = b;"#;
+    assert_eq!(eval(&format!("{} a == b;", maps)), Value::Boolean(true));
+    assert_eq!(eval(&format!(r#"{} a == {{"x": 1}};"#, maps)), Value::Boolean(false));
+}
+
+#[test]
+fn cycles_through_lists_and_maps_compare_from_rust() {
+    let mut engine = Engine::new();
+    let left = engine.eval(r#"let m = {}; let l = [m]; m["l"] = l; l;"#).unwrap();
+    let right = engine.eval(r#"let n = {}; let k = [n]; n["l"] = k; k;"#).unwrap();
+
+    assert_eq!(left, left.clone());
+    assert_eq!(left, right);
+}
+
+#[test]
+fn nil_equals_nil() {
+    assert_eq!(eval_everywhere("nil == nil;"), Value::Boolean(true));
+    assert_eq!(eval_everywhere("nil != nil;"), Value::Boolean(false));
+    assert_eq!(eval_everywhere("let x = nil; x == nil;"), Value::Boolean(true));
+}
+
+#[test]
+fn values_of_different_types_are_not_equal() {
+    for pair in [r#"1, "1""#, "nil, 0", "true, 1", "1, 1.0", "[], {}", r#""", nil"#, "[nil], [0]"] {
+        let (left, right) = pair.split_once(", ").unwrap();
+        assert_eq!(eval_everywhere(&format!("{} == {};", left, right)), Value::Boolean(false), "{}", pair);
+        assert_eq!(eval_everywhere(&format!("{} != {};", left, right)), Value::Boolean(true), "{}", pair);
+    }
+}
+
+#[test]
+fn functions_are_equal_only_to_themselves() {
+    let source = "taco f() {} taco g() {}";
+    assert_eq!(eval_everywhere(&format!("{} f == f;", source)), Value::Boolean(true));
+    assert_eq!(eval_everywhere(&format!("{} f == g;", source)), Value::Boolean(false));
+    assert_eq!(eval_everywhere("len == len;"), Value::Boolean(true));
+}
+
+#[test]
+fn missing_map_keys_compare_equal_to_nil() {
+    let source = r#"let m = {a: 1, gone: nil};"#;
+    assert_eq!(eval_everywhere(&format!(r#"{} m["b"] == nil;"#, source)), Value::Boolean(true));
+    assert_eq!(eval_everywhere(&format!(r#"{} m["gone"] == nil;"#, source)), Value::Boolean(true));
+    assert_eq!(eval_everywhere(&format!(r#"{} m["a"] != nil;"#, source)), Value::Boolean(true));
+}
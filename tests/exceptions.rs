@@ -0,0 +1,109 @@
+use taco::{
+    interpreter::{
+        interpreter::Backend,
+        limits::Limits,
+        output::{Buffer, Output},
+    },
+    Engine, Error,
+};
+
+const BACKENDS: [Backend; 2] = [Backend::Tree, Backend::Vm];
+
+/// Runs `source` on `backend` and returns what it printed, followed by the
+/// message of any error that escaped.
+fn run(backend: Backend, limits: Limits, source: &str) -> String {
+    let buffer = Buffer::new();
+    let mut engine = Engine::with_output(Output::new(buffer.clone(), buffer.clone()));
+    engine.interpreter().set_backend(backend);
+    engine.interpreter().set_limits(limits);
+    match engine.eval(source) {
+        Ok(_) => buffer.contents(),
+        Err(Error::Runtime(error)) => format!("{}error: {}", buffer.contents(), error.get_message()),
+        Err(error) => panic!("{:?} did not parse: {}", source, error),
+    }
+}
+
+fn assert_prints(source: &str, expected: &str) {
+    for backend in BACKENDS {
+        assert_eq!(run(backend, Limits::default(), source), expected, "on {:?}", backend);
+    }
+}
+
+#[test]
+fn an_error_in_finally_replaces_the_thrown_one() {
+    assert_prints(
+        r#"taco f() { try { throw "body"; } finally { throw "finally"; } }
+           try { f(); } catch (e) { print e; }"#,
+        "finally\n",
+    );
+    assert_prints(
+        r#"taco f() { try { throw "body"; } catch (e) { throw "catch"; } finally { nil(); } }
+           try { f(); } catch (e) { print e.message; }"#,
+        "Cannot call a value of type nil.\n",
+    );
+}
+
+#[test]
+fn a_return_in_finally_replaces_everything_else() {
+    assert_prints(
+        r#"taco thrown() { try { throw "body"; } finally { return "finally"; } }
+           taco returned() { try { return "body"; } finally { return "finally"; } }
+           taco caught() { try { throw "body"; } catch (e) { return "catch"; } finally { return "finally"; } }
+           print thrown(); print returned(); print caught();"#,
+        "finally\nfinally\nfinally\n",
+    );
+}
+
+#[test]
+fn finally_runs_without_changing_the_result() {
+    assert_prints(
+        r#"taco f() { try { return "body"; } finally { print "cleanup"; } }
+           taco g() { try { throw "x"; } catch (e) { return "caught " + e; } finally { print "cleanup"; } }
+           print f(); print g();"#,
+        "cleanup\nbody\ncleanup\ncaught x\n",
+    );
+    assert_prints(
+        r#"try { throw "escaped"; } finally { print "cleanup"; }"#,
+        "cleanup\nerror: escaped",
+    );
+}
+
+#[test]
+fn errors_can_be_rethrown_from_catch() {
+    assert_prints(
+        r#"taco f() { try { throw "first"; } catch (e) { throw e + " again"; } finally { print "cleanup"; } }
+           try { f(); } catch (e) { print e; }"#,
+        "cleanup\nfirst again\n",
+    );
+
+    // A rethrown error keeps the line it was first raised on.
+    assert_prints(
+        "taco f() {\n  try { nil(); } catch (e) { throw e; }\n}\ntry { f(); } catch (e) { print e.line; }",
+        "2\n",
+    );
+}
+
+#[test]
+fn stack_overflows_can_be_caught() {
+    assert_prints(
+        r#"taco deep() { return deep(); }
+           try { deep(); } catch (e) { print e.message; } finally { print "cleanup"; }"#,
+        "Stack overflow.\ncleanup\n",
+    );
+}
+
+#[test]
+fn limits_skip_catch_and_finally() {
+    // Once a limit is hit every further step fails with it, so a finally
+    // block could not run anyway; the limit stops the script at once.
+    let limits = Limits { max_steps: Some(1000), ..Limits::default() };
+    for backend in BACKENDS {
+        let source = r#"
+            taco f() {
+                try { while (true) {} } catch (e) { print "caught"; } finally { print "finally"; return 1; }
+            }
+            try { f(); } catch (e) { print "outer"; } finally { print "outer finally"; }
+        "#;
+        assert_eq!(run(backend, limits, source), "error: Step limit of 1000 exceeded.", "on {:?}", backend);
+    }
+}
@@ -0,0 +1,132 @@
+use std::{fs, path::PathBuf};
+
+use taco::{interpreter::interpreter::Backend, Engine, Value};
+
+/// A fresh, empty directory for one test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("taco-fs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs `call` inside a try block on both backends, returning the message
+/// it was caught with.
+fn caught(call: &str) -> String {
+    let source = format!(
+        "let caught = nil; try {{ {} }} catch (e) {{ caught = e.message; }} caught;",
+        call
+    );
+
+    let mut messages = Vec::new();
+    for backend in [Backend::Tree, Backend::Vm] {
+        let mut engine = Engine::new();
+        engine.interpreter().set_backend(backend);
+        match engine.eval(&source) {
+            Ok(Value::String(message)) => messages.push(message),
+            other => panic!("expected {:?} to be caught on {:?}, got {:?}", call, backend, other),
+        }
+    }
+
+    assert_eq!(messages[0], messages[1]);
+    messages.remove(0)
+}
+
+#[test]
+fn missing_files_are_caught() {
+    let dir = temp_dir("missing");
+    let path = dir.join("nope.txt").display().to_string();
+
+    let message = caught(&format!("fs.read_file({:?});", path));
+    assert!(message.starts_with(&format!("Could not read '{}': ", path)), "{}", message);
+
+    let message = caught(&format!("fs.list_dir({:?});", path));
+    assert!(message.starts_with(&format!("Could not list '{}': ", path)), "{}", message);
+
+    let nested = dir.join("no/such/dir.txt").display().to_string();
+    let message = caught(&format!("fs.write_file({:?}, \"x\");", nested));
+    assert!(message.starts_with(&format!("Could not write '{}': ", nested)), "{}", message);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn directories_are_not_files() {
+    let dir = temp_dir("directory");
+    let path = dir.display().to_string();
+
+    let message = caught(&format!("fs.read_file({:?});", path));
+    assert!(message.starts_with(&format!("Could not read '{}': ", path)), "{}", message);
+
+    let message = caught(&format!("fs.append_file({:?}, \"x\");", path));
+    assert!(message.starts_with(&format!("Could not append to '{}': ", path)), "{}", message);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn files_that_are_not_utf8_are_caught() {
+    let dir = temp_dir("utf8");
+    let path = dir.join("binary.dat");
+    fs::write(&path, [0x66, 0x6f, 0xff, 0xfe]).unwrap();
+    let path = path.display().to_string();
+
+    let message = caught(&format!("fs.read_file({:?});", path));
+    assert!(message.starts_with(&format!("Could not read '{}': ", path)), "{}", message);
+    assert!(message.contains("UTF-8"), "{}", message);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn permission_denied_is_caught() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = temp_dir("permission");
+    let path = dir.join("secret.txt");
+    fs::write(&path, "hidden").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o000)).unwrap();
+
+    // Permissions mean nothing to root, so there is nothing to test there.
+    if fs::read(&path).is_err() {
+        let path = path.display().to_string();
+        let message = caught(&format!("fs.read_file({:?});", path));
+        assert!(message.starts_with(&format!("Could not read '{}': ", path)), "{}", message);
+        assert!(message.contains("ermission denied"), "{}", message);
+
+        let message = caught(&format!("fs.write_file({:?}, \"x\");", path));
+        assert!(message.starts_with(&format!("Could not write '{}': ", path)), "{}", message);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn bad_arguments_are_caught() {
+    assert_eq!(caught("fs.read_file(1);"), "fs.read_file() expects a string but got integer.");
+    assert_eq!(caught(r#"fs.write_file("x", nil);"#), "fs.write_file() expects a string but got nil.");
+    assert_eq!(caught(r#"io.eprint();"#), "io.eprint() expected 1 arguments but got 0.");
+}
+
+#[test]
+fn files_round_trip() {
+    let dir = temp_dir("round-trip");
+    let path = dir.join("notes.txt").display().to_string();
+
+    let source = format!(
+        r#"let path = {:?};
+           fs.write_file(path, "héllo");
+           fs.append_file(path, "!");
+           [fs.exists(path), fs.read_file(path), fs.list_dir({:?})];"#,
+        path,
+        dir.display().to_string()
+    );
+    let value = Engine::new().eval(&source).unwrap();
+    assert_eq!(
+        value,
+        Value::list(vec![Value::Boolean(true), Value::from("héllo!"), Value::from(vec!["notes.txt"])])
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
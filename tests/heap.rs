@@ -0,0 +1,102 @@
+use std::fs;
+
+use taco::{
+    interpreter::{
+        heap,
+        map::{HashKey, ValueMap},
+    },
+    Engine, Value,
+};
+
+// The collector's bookkeeping is per thread, and every test runs on its own
+// thread, so the counts here only see each test's objects.
+
+const MAKE_CYCLE: &str = r#"
+taco make() {
+    let list = [nil];
+    let map = {list: list};
+    list[0] = map;
+    taco get() { return map; }
+    map["get"] = get;
+    return nil;
+}
+"#;
+
+#[test]
+fn cycles_between_lists_maps_and_closures_are_freed() {
+    let mut engine = Engine::new();
+    engine.eval(MAKE_CYCLE).unwrap();
+    engine.interpreter().collect_garbage();
+    let baseline = heap::tracked();
+
+    for _ in 0..10 {
+        engine.call_function("make", vec![]).unwrap();
+    }
+    assert!(heap::tracked() > baseline);
+
+    engine.interpreter().collect_garbage();
+
+    assert_eq!(heap::tracked(), baseline);
+}
+
+#[test]
+fn nothing_is_left_once_the_engine_is_dropped() {
+    let mut engine = Engine::new();
+    engine.eval(MAKE_CYCLE).unwrap();
+    engine.eval("let kept = [nil]; kept[0] = {kept: kept}; make();").unwrap();
+    drop(engine);
+
+    heap::collect();
+    assert_eq!(heap::tracked(), 0);
+}
+
+#[test]
+fn reachable_cycles_are_kept() {
+    let mut engine = Engine::new();
+    engine.eval(MAKE_CYCLE).unwrap();
+    engine.eval("let list = [nil]; let map = {list: list}; list[0] = map;").unwrap();
+
+    engine.interpreter().collect_garbage();
+
+    let kept = engine.eval(r#"map["list"][0] == map;"#).unwrap();
+    assert_eq!(kept, Value::Boolean(true));
+}
+
+#[test]
+fn cycles_built_from_rust_are_freed() {
+    let list = Value::list(vec![Value::Nil]);
+    let map = Value::map(ValueMap::new());
+    if let (Value::List(elements), Value::Map(entries)) = (&list, &map) {
+        elements.borrow_mut()[0] = map.clone();
+        entries.borrow_mut().insert(HashKey::String(String::from("list")), list.clone());
+    }
+    assert_eq!(heap::tracked(), 2);
+
+    drop(list);
+    drop(map);
+    assert_eq!(heap::tracked(), 2);
+
+    assert_eq!(heap::collect(), 2);
+    assert_eq!(heap::tracked(), 0);
+}
+
+#[test]
+fn modules_stored_in_themselves_are_freed() {
+    let dir = std::env::temp_dir().join(format!("taco-heap-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("lib.taco"), "export let data = [1, 2, 3]; export taco get() { return data; }").unwrap();
+
+    for _ in 0..20 {
+        let mut engine = Engine::new();
+        engine.interpreter().set_main_file(&dir.join("main.taco"));
+        engine
+            .eval(r#"import "./lib.taco" as lib; lib["self"] = lib; math["self"] = math;"#)
+            .unwrap();
+        drop(engine);
+
+        heap::collect();
+        assert_eq!(heap::tracked(), 0);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,119 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use taco::{interpreter::interpreter::Backend, Engine, Error, Value};
+
+/// A fresh directory holding the given files.
+fn fixture(name: &str, files: &[(&str, &[u8])]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("taco-imports-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, contents) in files {
+        fs::write(dir.join(file), contents).unwrap();
+    }
+    dir
+}
+
+/// Imports `path` from a main file in `dir` inside a try block, on both
+/// backends, returning the message it was caught with.
+fn caught(dir: &Path, path: &str) -> String {
+    let source = format!(
+        r#"let caught = nil; try {{ import "{}" as m; }} catch (e) {{ caught = e.message; }} caught;"#,
+        path
+    );
+
+    let mut messages = Vec::new();
+    for backend in [Backend::Tree, Backend::Vm] {
+        let mut engine = Engine::new();
+        engine.interpreter().set_backend(backend);
+        engine.interpreter().set_main_file(&dir.join("main.taco"));
+        match engine.eval(&source) {
+            Ok(Value::String(message)) => messages.push(message),
+            other => panic!("expected importing {:?} to be caught on {:?}, got {:?}", path, backend, other),
+        }
+    }
+
+    assert_eq!(messages[0], messages[1]);
+    messages.remove(0)
+}
+
+#[test]
+fn missing_modules_are_caught() {
+    let dir = fixture("missing", &[]);
+    let message = caught(&dir, "./nope.taco");
+    assert!(message.starts_with("Cannot import './nope.taco': "), "{}", message);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn directories_are_not_modules() {
+    let dir = fixture("directory", &[]);
+    fs::create_dir(dir.join("lib.taco")).unwrap();
+    let message = caught(&dir, "./lib.taco");
+    assert!(message.starts_with("Cannot import './lib.taco': "), "{}", message);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn modules_that_are_not_utf8_are_caught() {
+    let dir = fixture("utf8", &[("lib.taco", &[0x6c, 0x65, 0x74, 0xff])]);
+    let message = caught(&dir, "./lib.taco");
+    assert!(message.starts_with("Cannot import './lib.taco': "), "{}", message);
+    assert!(message.contains("UTF-8"), "{}", message);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn modules_with_syntax_errors_are_caught() {
+    let dir = fixture("syntax", &[("lib.taco", b"\nexport let = 1;")]);
+    let message = caught(&dir, "./lib.taco");
+    assert_eq!(message, "Cannot import './lib.taco': [Line 2] Expect variable name.");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn unreadable_modules_are_caught() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = fixture("permission", &[("lib.taco", b"export let x = 1;")]);
+    let path = dir.join("lib.taco");
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o000)).unwrap();
+
+    // Permissions mean nothing to root, so there is nothing to test there.
+    if fs::read(&path).is_err() {
+        let message = caught(&dir, "./lib.taco");
+        assert!(message.starts_with("Cannot import './lib.taco': "), "{}", message);
+        assert!(message.contains("ermission denied"), "{}", message);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cycles_are_caught() {
+    let dir = fixture(
+        "cycle",
+        &[("a.taco", br#"import "./b.taco" as b;"#), ("b.taco", br#"import "./a.taco" as a;"#)],
+    );
+    let message = caught(&dir, "./a.taco");
+    assert!(message.starts_with("Import cycle detected: "), "{}", message);
+    assert!(message.ends_with("a.taco"), "{}", message);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn uncaught_import_errors_point_at_the_import() {
+    let dir = fixture("location", &[]);
+    let mut engine = Engine::new();
+    engine.interpreter().set_main_file(&dir.join("main.taco"));
+
+    match engine.eval("let x = 1;\n\nimport \"./nope.taco\" as m;") {
+        Err(Error::Runtime(error)) => assert_eq!(error.get_token().get_line(), 3),
+        other => panic!("expected the import to fail, got {:?}", other),
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "serde")]
+
+use taco::{Engine, Error, Value};
+
+fn eval(source: &str) -> Value {
+    Engine::new().eval(source).unwrap()
+}
+
+fn error_message(source: &str) -> String {
+    match Engine::new().eval(source) {
+        Err(Error::Runtime(error)) => error.get_message().to_string(),
+        other => panic!("expected {:?} to fail, got {:?}", source, other),
+    }
+}
+
+fn string(text: &str) -> Value {
+    Value::String(text.to_string())
+}
+
+#[test]
+fn parse_and_stringify_round_trip() {
+    let text = r#"{"name":"taco","tags":["a","b"],"size":3,"ratio":0.5,"open":true,"note":null}"#;
+    let source = format!("json.stringify(json.parse(`{}`));", text);
+    assert_eq!(eval(&source), string(text));
+
+    assert_eq!(eval("json.parse(`[1, [2, {}]]`)[1][0];"), Value::Integer(2));
+    assert_eq!(eval(r#"json.parse(`"héllo"`);"#), string("héllo"));
+}
+
+#[test]
+fn nil_is_null() {
+    assert_eq!(eval("json.stringify(nil);"), string("null"));
+    assert_eq!(eval("json.stringify([nil, {a: nil}]);"), string(r#"[null,{"a":null}]"#));
+    assert_eq!(eval(r#"json.parse("null");"#), Value::Nil);
+    assert_eq!(eval(r#"json.parse(`{"a": null}`)["a"] == nil;"#), Value::Boolean(true));
+}
+
+#[test]
+fn floats_and_integers_stay_apart() {
+    assert_eq!(eval(r#"json.parse("1");"#), Value::Integer(1));
+    assert_eq!(eval(r#"json.parse("1.0");"#), Value::Float(1.0));
+    assert_eq!(eval(r#"json.parse("-0.25");"#), Value::Float(-0.25));
+    assert_eq!(eval("json.stringify([1, 1.0, 2.5]);"), string("[1,1.0,2.5]"));
+
+    // Too large for an integer, so it can only be a float.
+    assert_eq!(eval(r#"json.parse("18446744073709551615");"#), Value::Float(u64::MAX as f64));
+}
+
+#[test]
+fn containers_that_contain_themselves_are_errors() {
+    assert_eq!(
+        error_message("let xs = [1]; xs[0] = xs; json.stringify(xs);"),
+        "Cannot convert to JSON: found a list that contains itself."
+    );
+    assert_eq!(
+        error_message(r#"let m = {}; m["self"] = m; json.stringify({outer: m});"#),
+        "Cannot convert to JSON: found a map that contains itself."
+    );
+
+    // The same list twice is not a cycle.
+    assert_eq!(eval("let xs = [1]; json.stringify([xs, xs]);"), string("[[1],[1]]"));
+}
+
+#[test]
+fn values_without_a_json_form_are_errors() {
+    assert_eq!(
+        error_message("taco f() {} json.stringify([f]);"),
+        "Cannot convert to JSON: found a function, which has no serialized form."
+    );
+}
+
+#[test]
+fn malformed_json_is_an_error() {
+    for text in [r#"{"a": }"#, "[1, 2", "tru", "", r#"{"a": 1} x"#] {
+        let message = error_message(&format!("json.parse(`{}`);", text));
+        assert!(message.starts_with("Invalid JSON: "), "{:?} gave {:?}", text, message);
+    }
+
+    assert_eq!(error_message("json.parse(1);"), "json.parse() expects a string but got integer.");
+}
+
+#[test]
+fn map_keys() {
+    // JSON object keys are strings, so integer and boolean keys are written
+    // as strings and read back as strings.
+    assert_eq!(eval("json.stringify({1: 2});"), string(r#"{"1":2}"#));
+    assert_eq!(eval(r#"json.parse(json.stringify({1: 2}))["1"];"#), Value::Integer(2));
+    assert_eq!(eval("let m = {}; m[true] = 1; json.stringify(m);"), string(r#"{"true":1}"#));
+
+    // Nil has no string form.
+    let message = error_message("let m = {}; m[nil] = 1; json.stringify(m);");
+    assert!(message.starts_with("Cannot convert to JSON: key must be a string"), "{}", message);
+}
+
+#[test]
+fn pretty_output_is_indented() {
+    assert_eq!(
+        eval("json.stringify({a: [1, 2], b: {}}, true);"),
+        string("{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": {}\n}")
+    );
+    assert_eq!(eval("json.stringify([1], false);"), string("[1]"));
+    assert_eq!(
+        error_message("json.stringify([1], 1);"),
+        "json.stringify() expects a boolean for 'pretty' but got integer."
+    );
+}
+
+#[test]
+fn values_serialize_from_rust() {
+    let value = eval(r#"{list: [1, 2.5, "x"], nothing: nil};"#);
+    assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"list":[1,2.5,"x"],"nothing":null}"#);
+
+    let back: Value = serde_json::from_str(r#"{"list":[1,2.5,"x"],"nothing":null}"#).unwrap();
+    assert_eq!(back, value);
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use taco::{
+    error::interpreter::ErrorKind,
+    interpreter::{
+        interpreter::Backend,
+        limits::Limits,
+        native::Arity,
+        output::{Buffer, Output},
+    },
+    Engine, Error,
+};
+
+const BACKENDS: [Backend; 2] = [Backend::Tree, Backend::Vm];
+
+fn engine(backend: Backend, limits: Limits, buffer: &Buffer) -> Engine {
+    let mut engine = Engine::with_output(Output::new(buffer.clone(), buffer.clone()));
+    engine.interpreter().set_backend(backend);
+    engine.interpreter().set_limits(limits);
+    engine
+}
+
+fn limit_hit(engine: &mut Engine, source: &str) -> ErrorKind {
+    match engine.eval(source) {
+        Err(Error::Runtime(error)) => error.get_kind(),
+        other => panic!("expected a limit to stop {:?}, got {:?}", source, other),
+    }
+}
+
+#[test]
+fn step_limit() {
+    let limits = Limits { max_steps: Some(1000), ..Limits::default() };
+    for backend in BACKENDS {
+        let mut engine = engine(backend, limits, &Buffer::new());
+        assert_eq!(limit_hit(&mut engine, "while (true) {}"), ErrorKind::StepLimit);
+    }
+}
+
+#[test]
+fn allocation_limit() {
+    let limits = Limits { max_allocation: Some(10_000), ..Limits::default() };
+    for backend in BACKENDS {
+        let mut engine = engine(backend, limits, &Buffer::new());
+        let kind = limit_hit(&mut engine, r#"let s = ""; while (true) { s = s + "0123456789"; }"#);
+        assert_eq!(kind, ErrorKind::AllocationLimit);
+    }
+}
+
+#[test]
+fn the_allocation_limit_counts_values_already_freed() {
+    let limits = Limits { max_allocation: Some(10_000), ..Limits::default() };
+    for backend in BACKENDS {
+        let mut engine = engine(backend, limits, &Buffer::new());
+        // Each string is dropped straight away, so little is ever held at
+        // once, but about 20,000 bytes are allocated in total.
+        let source = r#"let i = 0; while (i < 2000) { let s = "01234" + "56789"; i = i + 1; }"#;
+        assert_eq!(limit_hit(&mut engine, source), ErrorKind::AllocationLimit);
+
+        // A quarter as many stays under it.
+        let source = r#"let i = 0; while (i < 500) { let s = "01234" + "56789"; i = i + 1; }"#;
+        engine.eval(source).unwrap();
+        assert!(engine.interpreter().get_usage().allocated <= 10_000);
+    }
+}
+
+#[test]
+fn output_limit() {
+    let limits = Limits { max_output: Some(100), ..Limits::default() };
+    for backend in BACKENDS {
+        let buffer = Buffer::new();
+        let mut engine = engine(backend, limits, &buffer);
+        assert_eq!(limit_hit(&mut engine, r#"while (true) { print "spam"; }"#), ErrorKind::OutputLimit);
+        assert!(buffer.contents().len() <= 100);
+    }
+}
+
+#[test]
+fn time_limit() {
+    let limits = Limits { timeout: Some(Duration::from_millis(50)), ..Limits::default() };
+    for backend in BACKENDS {
+        let mut engine = engine(backend, limits, &Buffer::new());
+        assert_eq!(limit_hit(&mut engine, "while (true) {}"), ErrorKind::Timeout);
+    }
+}
+
+#[test]
+fn scripts_cannot_catch_limits() {
+    let limits = Limits { max_steps: Some(1000), ..Limits::default() };
+    for backend in BACKENDS {
+        let buffer = Buffer::new();
+        let mut engine = engine(backend, limits, &buffer);
+        let source = r#"
+            try { while (true) {} } catch (e) { print "caught"; } finally { print "finally"; }
+            print "after";
+        "#;
+        assert_eq!(limit_hit(&mut engine, source), ErrorKind::StepLimit);
+        assert!(!buffer.contents().contains("caught"));
+        assert!(!buffer.contents().contains("after"));
+    }
+}
+
+#[test]
+fn host_callbacks_share_the_callers_budget() {
+    let limits = Limits { max_steps: Some(5000), ..Limits::default() };
+    for backend in BACKENDS {
+        let mut engine = engine(backend, limits, &Buffer::new());
+        engine.define_native("call_back", Arity::Exact(1), |interpreter, mut args| {
+            interpreter
+                .call_value(args.remove(0), vec![])
+                .map_err(|error| error.get_message().to_string())
+        });
+
+        // Each callback stays well under the limit, but together they don't.
+        let source = "
+            taco work() { let i = 0; while (i < 10) { i = i + 1; } }
+            let n = 0;
+            while (n < 1000) { call_back(work); n = n + 1; }
+        ";
+        assert_eq!(limit_hit(&mut engine, source), ErrorKind::StepLimit);
+    }
+}
+
+#[test]
+fn each_host_run_gets_a_fresh_budget() {
+    let limits = Limits { max_steps: Some(1000), ..Limits::default() };
+    for backend in BACKENDS {
+        let mut engine = engine(backend, limits, &Buffer::new());
+        engine.eval("taco work() { let i = 0; while (i < 20) { i = i + 1; } }").unwrap();
+        for _ in 0..20 {
+            engine.call_function("work", vec![]).unwrap();
+        }
+    }
+}
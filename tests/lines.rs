@@ -0,0 +1,32 @@
+use taco::{Engine, Error};
+
+// Line numbers used to be counted in a byte, and wrapped after line 255.
+
+fn after_blank_lines(lines: usize, source: &str) -> String {
+    "\n".repeat(lines) + source
+}
+
+#[test]
+fn scan_errors_report_lines_past_255() {
+    match Engine::new().eval(&after_blank_lines(299, "let a = 1 @ 2;")) {
+        Err(Error::Parse(error)) => assert_eq!(error.get_token()[0].get_line(), 300),
+        other => panic!("expected a scan error, got {:?}", other),
+    }
+}
+
+#[test]
+fn parse_errors_report_lines_past_255() {
+    match Engine::new().eval(&after_blank_lines(299, "let = 1;")) {
+        Err(Error::Parse(error)) => assert_eq!(error.get_token()[0].get_line(), 300),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn runtime_errors_report_lines_past_255() {
+    let source = "let s = `one\ntwo`;\n".repeat(150) + "undefined_name;";
+    match Engine::new().eval(&source) {
+        Err(Error::Runtime(error)) => assert_eq!(error.get_token().get_line(), 301),
+        other => panic!("expected a runtime error, got {:?}", other),
+    }
+}
@@ -0,0 +1,45 @@
+use std::{fs, path::PathBuf};
+
+use taco::{parser::parser::Parser, token::scanner::Scanner, Engine, Value};
+
+/// A fresh directory holding `sub/lib.taco`, which exports `x`.
+fn fixture(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("taco-{}-{}", name, std::process::id()));
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("sub/lib.taco"), "export let x = 42;").unwrap();
+    dir
+}
+
+#[test]
+fn imports_resolve_relative_to_the_main_file_while_it_runs() {
+    let dir = fixture("main-file");
+    let mut engine = Engine::new();
+    let source = r#"import "./lib.taco" as lib; lib.x;"#;
+
+    let result = engine.interpreter().with_main_file(&dir.join("sub/main.taco"), |interpreter| {
+        let mut scanner = Scanner::new(source.to_string());
+        let statements = Parser::new(scanner.scan_tokens()).parse().unwrap();
+        interpreter.interpret_value(statements)
+    });
+    assert_eq!(result.unwrap(), Value::Integer(42));
+
+    // Afterwards there is no main file again, so the same import fails.
+    assert!(engine.eval(source).is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn the_previous_main_file_is_restored() {
+    let dir = fixture("restore");
+    fs::write(dir.join("main.taco"), "").unwrap();
+    let mut engine = Engine::new();
+    engine.interpreter().set_main_file(&dir.join("main.taco"));
+
+    engine.interpreter().with_main_file(&dir.join("sub/other.taco"), |_| ());
+
+    let value = engine.eval(r#"import "./sub/lib.taco" as lib; lib.x;"#).unwrap();
+    assert_eq!(value, Value::Integer(42));
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,111 @@
+use taco::{
+    interpreter::{
+        interpreter::Backend,
+        output::{Buffer, Output},
+    },
+    optimizer::optimizer::optimize,
+    parser::parser::Parser,
+    token::scanner::Scanner,
+    Engine,
+};
+
+/// Runs `source` and returns what it printed, followed by its result or
+/// its error.
+fn run(backend: Backend, optimize: bool, source: &str) -> String {
+    let buffer = Buffer::new();
+    let mut engine = Engine::with_output(Output::new(buffer.clone(), buffer.clone()));
+    engine.interpreter().set_backend(backend);
+    engine.interpreter().set_optimize(optimize);
+    let outcome = match engine.eval(source) {
+        Ok(value) => format!("=> {:?}", value),
+        Err(error) => format!("error: {}", error),
+    };
+    buffer.contents() + &outcome
+}
+
+/// How many top-level statements are left after optimizing `source`.
+fn optimized_len(source: &str) -> usize {
+    let mut scanner = Scanner::new(source.to_string());
+    optimize(Parser::new(scanner.scan_tokens()).parse().unwrap()).len()
+}
+
+/// Checks the optimizer changes nothing a program can observe, on either
+/// backend, and returns what the program did.
+fn assert_unchanged(source: &str) -> String {
+    let expected = run(Backend::Tree, false, source);
+    for backend in [Backend::Tree, Backend::Vm] {
+        for optimize in [false, true] {
+            let actual = run(backend, optimize, source);
+            assert_eq!(actual, expected, "{:?} with optimize {} changed:\n{}", backend, optimize, source);
+        }
+    }
+    expected
+}
+
+#[test]
+fn failing_operations_are_not_folded() {
+    let cases = [
+        ("1 / 0;", "Division by zero"),
+        ("9223372036854775807 + 1;", "Integer overflow"),
+        (r#""a" - 1;"#, "Invalid operands for subtraction"),
+        (r#"-"x";"#, "Invalid operand for unary minus"),
+    ];
+    for (source, message) in cases {
+        let outcome = assert_unchanged(&format!(r#"print "before"; {} print "after";"#, source));
+        assert!(outcome.starts_with("before\nerror: "), "{}", outcome);
+        assert!(outcome.contains(message), "{}", outcome);
+        assert!(!outcome.contains("after"), "{}", outcome);
+    }
+}
+
+#[test]
+fn constants_are_folded() {
+    assert_eq!(assert_unchanged("1 + 2 * 3;"), "=> Integer(7)");
+    assert_eq!(assert_unchanged(r#""ta" + "co";"#), r#"=> String("taco")"#);
+    assert_eq!(assert_unchanged("-(1 - 3) == 2;"), "=> Boolean(true)");
+}
+
+#[test]
+fn dead_branches_and_loops_are_removed() {
+    let source = r#"
+        if (false) { print "then"; } else { print "else"; }
+        if (1 > 2) print "never";
+        while (false) { print "never"; }
+        while (nil) { print "never"; }
+        print "done";
+    "#;
+    assert_eq!(assert_unchanged(source), "else\ndone\n=> Nil");
+
+    assert_eq!(optimized_len(r#"if (false) { print "never"; }"#), 0);
+    assert_eq!(optimized_len(r#"while (false) { print "never"; }"#), 0);
+    assert_eq!(optimized_len(r#"while (0 > 1) { print "never"; }"#), 0);
+    assert_eq!(optimized_len(r#"if (f()) { print "maybe"; }"#), 1);
+    assert_eq!(optimized_len(r#"while (f()) { print "maybe"; }"#), 1);
+}
+
+#[test]
+fn conditions_with_side_effects_still_run() {
+    let source = r#"
+        let n = 0;
+        taco tick() { n = n + 1; return false; }
+        if (tick()) { print "never"; }
+        if (tick() or false) { print "never"; }
+        while (tick()) { print "never"; }
+        while (false and tick()) { print "never"; }
+        n;
+    "#;
+    assert_eq!(assert_unchanged(source), "=> Integer(3)");
+}
+
+#[test]
+fn logical_operators_still_short_circuit() {
+    let source = r#"
+        taco f() { print "called"; return "f"; }
+        print false and f();
+        print true or f();
+        print false or f();
+        print true and f();
+        nil or "default";
+    "#;
+    assert_eq!(assert_unchanged(source), "false\ntrue\ncalled\nf\ncalled\nf\n=> String(\"default\")");
+}
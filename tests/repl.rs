@@ -0,0 +1,98 @@
+use taco::{
+    interpreter::{
+        interpreter::{Backend, Interpreter, Value},
+        native::Arity,
+        output::{Buffer, Output},
+    },
+    lang::{repl::run_command, taco::Taco},
+    run::exec_repl,
+};
+
+fn repl(buffer: &Buffer) -> Taco {
+    let mut taco = Taco::new();
+    taco.interpreter = Some(Interpreter::with_output(Output::new(buffer.clone(), buffer.clone())));
+    taco
+}
+
+#[test]
+fn values_are_echoed_to_the_output_sink() {
+    let buffer = Buffer::new();
+    let mut taco = repl(&buffer);
+
+    exec_repl(String::from("1 + 2;"), &mut taco);
+    exec_repl(String::from(r#""taco";"#), &mut taco);
+
+    assert_eq!(buffer.contents(), "=> 3\n=> \"taco\"\n");
+}
+
+#[test]
+fn nil_and_statements_are_not_echoed() {
+    let buffer = Buffer::new();
+    let mut taco = repl(&buffer);
+
+    exec_repl(String::from("let x = 1;"), &mut taco);
+    exec_repl(String::from("nil;"), &mut taco);
+    exec_repl(String::from("print x;"), &mut taco);
+
+    assert_eq!(buffer.contents(), "1\n");
+}
+
+#[test]
+fn definitions_survive_between_inputs() {
+    for backend in [Backend::Tree, Backend::Vm] {
+        let buffer = Buffer::new();
+        let mut taco = repl(&buffer);
+        taco.interpreter().set_backend(backend);
+
+        exec_repl(String::from("let total = 1;"), &mut taco);
+        exec_repl(String::from("taco add(n) { total = total + n; return total; }"), &mut taco);
+        exec_repl(String::from("add(2);"), &mut taco);
+        exec_repl(String::from("let xs = [total];"), &mut taco);
+        exec_repl(String::from("xs[0] + add(10);"), &mut taco);
+
+        assert_eq!(buffer.contents(), "=> 3\n=> 16\n", "on {:?}", backend);
+    }
+}
+
+#[test]
+fn errors_do_not_lose_earlier_definitions() {
+    let buffer = Buffer::new();
+    let mut taco = repl(&buffer);
+
+    exec_repl(String::from("let x = 1;"), &mut taco);
+    exec_repl(String::from("x = x + nil;"), &mut taco);
+    exec_repl(String::from("let = ;"), &mut taco);
+    buffer.clear();
+    exec_repl(String::from("x;"), &mut taco);
+
+    assert_eq!(buffer.contents(), "=> 1\n");
+}
+
+#[test]
+fn reset_forgets_definitions_but_keeps_builtins() {
+    let buffer = Buffer::new();
+    let mut taco = repl(&buffer);
+    taco.interpreter().define_native("host", Arity::Exact(0), |_, _| Ok(Value::Integer(7)));
+
+    exec_repl(String::from("let x = 1;"), &mut taco);
+    exec_repl(String::from("taco f() { return x; }"), &mut taco);
+    exec_repl(String::from("let len = 5;"), &mut taco);
+    let _ = run_command("reset", &mut taco);
+
+    assert!(taco.interpreter().top_level_bindings().is_empty());
+    buffer.clear();
+    exec_repl(String::from("len([1, 2]);"), &mut taco);
+    exec_repl(String::from("math.abs(-3);"), &mut taco);
+    exec_repl(String::from("host();"), &mut taco);
+    assert_eq!(buffer.contents(), "=> 2\n=> 3\n=> 7\n");
+
+    buffer.clear();
+    exec_repl(String::from("f;"), &mut taco);
+    assert!(buffer.contents().contains("Undefined variable 'f'"), "{}", buffer.contents());
+
+    // The old names can be defined again.
+    buffer.clear();
+    exec_repl(String::from("let x = 2;"), &mut taco);
+    exec_repl(String::from("x;"), &mut taco);
+    assert_eq!(buffer.contents(), "=> 2\n");
+}
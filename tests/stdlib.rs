@@ -0,0 +1,121 @@
+use taco::{Engine, Error, Value};
+
+fn eval(source: &str) -> Result<Value, Error> {
+    Engine::new().eval(source)
+}
+
+fn error_message(source: &str) -> String {
+    match eval(source) {
+        Err(Error::Runtime(error)) => error.get_message().to_string(),
+        other => panic!("expected a runtime error from {:?}, got {:?}", source, other),
+    }
+}
+
+fn string(text: &str) -> Value {
+    Value::String(text.to_string())
+}
+
+#[test]
+fn int_truncates_floats_that_fit() {
+    assert_eq!(eval("int(-2.9);").unwrap(), Value::Integer(-2));
+    assert_eq!(eval(r#"int(float("1e18"));"#).unwrap(), Value::Integer(1_000_000_000_000_000_000));
+}
+
+#[test]
+fn int_rejects_floats_out_of_range() {
+    assert!(error_message(r#"int(float("1e300"));"#).contains("does not fit in an integer"));
+    assert!(error_message(r#"int(float("-1e300"));"#).contains("does not fit in an integer"));
+    assert!(error_message("int(9223372036854775808.0);").contains("does not fit in an integer"));
+}
+
+#[test]
+fn int_rejects_nan() {
+    assert!(error_message(r#"int(float("nan"));"#).contains("Cannot convert NaN"));
+}
+
+#[test]
+fn pow_reports_zero_to_a_negative_power() {
+    for source in ["math.pow(0, -1);", "math.pow(0.0, -2.5);", "math.pow(-0.0, -1);"] {
+        assert!(error_message(source).contains("math.pow() domain error"), "{}", source);
+    }
+}
+
+#[test]
+fn pow_reports_results_too_large_to_represent() {
+    assert!(error_message("math.pow(10.0, 400);").contains("math.pow() domain error"));
+    assert!(error_message("math.pow(-1, 0.5);").contains("math.pow() domain error"));
+    assert_eq!(eval("math.pow(2, -1);").unwrap(), Value::Float(0.5));
+}
+
+#[test]
+fn string_methods_count_characters_not_bytes() {
+    assert_eq!(eval(r#""héllo".len();"#).unwrap(), Value::Integer(5));
+    assert_eq!(eval(r#""🌮🌮".len();"#).unwrap(), Value::Integer(2));
+    assert_eq!(eval(r#"len("héllo");"#).unwrap(), Value::Integer(5));
+    assert_eq!(eval(r#""".len();"#).unwrap(), Value::Integer(0));
+    assert_eq!(eval(r#""héllo"[1];"#).unwrap(), string("é"));
+    assert_eq!(eval(r#""héllo"[-1];"#).unwrap(), string("o"));
+    assert_eq!(eval(r#""🌮ab"[0:2];"#).unwrap(), string("🌮a"));
+    assert_eq!(eval(r#""ÉCOLE".lower();"#).unwrap(), string("école"));
+    assert_eq!(eval(r#""é🌮".split("");"#).unwrap(), Value::from(vec!["é", "🌮"]));
+}
+
+#[test]
+fn split_handles_empty_input_and_separators() {
+    assert_eq!(eval(r#""abc".split("");"#).unwrap(), Value::from(vec!["a", "b", "c"]));
+    assert_eq!(eval(r#""".split("");"#).unwrap(), Value::from(Vec::<&str>::new()));
+    assert_eq!(eval(r#""".split(",");"#).unwrap(), Value::from(vec![""]));
+    assert_eq!(eval(r#""a,,b".split(",");"#).unwrap(), Value::from(vec!["a", "", "b"]));
+    assert_eq!(eval(r#"" a  b ".split();"#).unwrap(), Value::from(vec!["a", "b"]));
+    assert_eq!(eval(r#""  ".split();"#).unwrap(), Value::from(Vec::<&str>::new()));
+    assert_eq!(eval(r#""ab".replace("", "-");"#).unwrap(), string("-a-b-"));
+    assert_eq!(eval(r#""ab".contains("");"#).unwrap(), Value::Boolean(true));
+    assert_eq!(eval(r#""ab".starts_with("");"#).unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn string_indices_out_of_range_are_errors() {
+    assert_eq!(error_message(r#""héllo"[5];"#), "String index out of range.");
+    assert_eq!(error_message(r#""héllo"[-6];"#), "String index out of range.");
+    assert_eq!(error_message(r#"""[0];"#), "String index out of range.");
+    assert_eq!(error_message(r#""abc"[1.0];"#), "String indices must be integers, not float.");
+}
+
+#[test]
+fn string_slices_are_clamped() {
+    assert_eq!(eval(r#""abc"[1:10];"#).unwrap(), string("bc"));
+    assert_eq!(eval(r#""abc"[-10:2];"#).unwrap(), string("ab"));
+    assert_eq!(eval(r#""abc"[2:1];"#).unwrap(), string(""));
+    assert_eq!(eval(r#""abc"[5:];"#).unwrap(), string(""));
+    assert_eq!(error_message(r#""abc"["a":];"#), "Slice bounds must be integers, not string.");
+}
+
+#[test]
+fn string_methods_reject_wrong_arguments() {
+    assert_eq!(error_message(r#""a,b".split(1);"#), "split() expects a string argument but got integer.");
+    assert_eq!(error_message(r#""ab".contains(nil);"#), "contains() expects a string argument but got nil.");
+    assert_eq!(
+        error_message(r#""ab".replace("a", 1);"#),
+        "replace() expects a string argument but got integer."
+    );
+    assert_eq!(
+        error_message(r#""ab".starts_with(["a"]);"#),
+        "starts_with() expects a string argument but got list."
+    );
+    assert_eq!(error_message(r#""ab".len(1);"#), "len() expected 0 arguments but got 1.");
+    assert_eq!(error_message(r#""ab".replace("a");"#), "replace() expected 2 arguments but got 1.");
+    assert_eq!(error_message(r#""ab".reverse();"#), "Undefined property 'reverse' on string.");
+}
+
+#[test]
+fn trig_rejects_non_finite_angles() {
+    for function in ["sin", "cos", "tan"] {
+        for angle in [r#"float("inf")"#, r#"float("-inf")"#, r#"float("nan")"#] {
+            let message = error_message(&format!("math.{}({});", function, angle));
+            assert_eq!(message, format!("math.{}() domain error: argument must be finite.", function));
+        }
+    }
+
+    assert_eq!(eval("math.sin(0);").unwrap(), Value::Float(0.0));
+    assert_eq!(eval("math.cos(0.0);").unwrap(), Value::Float(1.0));
+}